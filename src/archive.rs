@@ -0,0 +1,40 @@
+use anyhow::{Context, Result, anyhow};
+use flate2::read::GzDecoder;
+use std::fs::{self, File};
+use std::path::Path;
+
+/// Whether `path` names an archive `capsync install` can unpack: `.tar.gz`,
+/// `.tgz`, or `.zip`.
+pub fn is_archive(path: &Path) -> bool {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_default();
+    name.ends_with(".tar.gz") || name.ends_with(".tgz") || name.ends_with(".zip")
+}
+
+/// Unpacks an archive into `target`. Entries that would land outside
+/// `target` are refused by the tar and zip readers.
+pub fn extract(archive: &Path, target: &Path) -> Result<()> {
+    fs::create_dir_all(target)
+        .with_context(|| format!("Failed to create directory {}", target.display()))?;
+    let file =
+        File::open(archive).with_context(|| format!("Failed to open {}", archive.display()))?;
+
+    let name = archive.to_string_lossy().to_ascii_lowercase();
+    if name.ends_with(".zip") {
+        zip::ZipArchive::new(file)
+            .and_then(|mut zip| zip.extract(target))
+            .with_context(|| format!("Failed to extract {}", archive.display()))
+    } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+        let mut tar = tar::Archive::new(GzDecoder::new(file));
+        tar.set_preserve_permissions(false);
+        tar.unpack(target)
+            .with_context(|| format!("Failed to extract {}", archive.display()))
+    } else {
+        Err(anyhow!(
+            "Unsupported archive {}; use .tar.gz, .tgz, or .zip",
+            archive.display()
+        ))
+    }
+}
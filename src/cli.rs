@@ -1,33 +1,83 @@
-use crate::clone::{CloneAction, CloneOptions, clone_skills};
-use crate::config::{self, Config, DestinationConfig};
+use crate::clone::{CloneAction, CloneOptions, OnExisting, clone_skills};
+use crate::config::{self, Config, DestinationConfig, LinkMode};
 use crate::detect::ToolDetector;
-use crate::install::{InstallOptions, install_skill};
-use crate::sync::SyncManager;
-use crate::tools::{all_tools, get_tool};
+use crate::doctor::{self, Finding};
+use crate::error::CapsyncError;
+use crate::install::{
+    ExistingSkill, InstallOptions, RepoInstallOptions, SkillSet, install_repository, install_skill,
+};
+use crate::list;
+use crate::lockfile::{self, Lockfile};
+use crate::paths;
+use crate::scaffold::{self, NewOptions};
+use crate::status::{self, LinkState, PathStatus};
+use crate::sync::{AdoptStrategy, PlannedAction, SourceKind, SyncManager};
+use crate::tools::{ToolRegistry, validate_registry};
+use crate::ui::{Prompter, Terminal};
+use crate::uninstall::{UninstallOptions, plan_uninstall, uninstall_skill};
+use crate::update::{UpdateOptions, UpdateStatus, update_skills};
+use crate::validate::{self, Severity};
 use anyhow::{Context, Result, anyhow};
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
+use serde::Serialize;
 use std::collections::HashMap;
-use std::io::{self, Write};
-use std::path::PathBuf;
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
 
 #[derive(Parser)]
 #[command(name = "capsync")]
 #[command(about = "A tool to sync agentic skills across multiple tools")]
 pub struct Cli {
+    /// Config file to use instead of ~/.config/capsync/config.toml [env: CAPSYNC_CONFIG]
+    #[arg(long, global = true, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+    /// Home directory that tool paths are resolved against [env: CAPSYNC_HOME]
+    #[arg(long, global = true, value_name = "DIR")]
+    pub home: Option<PathBuf>,
+    /// Output format for status, config, detect-tools, list and validate
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Text)]
+    pub format: OutputFormat,
     #[command(subcommand)]
     pub command: Commands,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    Text,
+    Json,
+}
+
 #[derive(Subcommand)]
 pub enum Commands {
     /// Initialize configuration file
-    Init,
+    Init {
+        /// Skills source directory; skips all prompts when given
+        #[arg(long)]
+        skills_source: Option<String>,
+        /// Commands source directory
+        #[arg(long)]
+        commands_source: Option<String>,
+        /// Enable a tool (repeatable)
+        #[arg(long, value_name = "TOOL")]
+        enable: Vec<String>,
+        /// Enable every detected tool
+        #[arg(long)]
+        detect: bool,
+        /// Overwrite an existing config without asking
+        #[arg(long)]
+        force: bool,
+    },
     /// Show current configuration
     Config,
     /// Auto-detect installed tools
     DetectTools,
     /// Sync skills to all enabled tools
-    Sync,
+    Sync {
+        /// Print the planned actions without touching the filesystem
+        #[arg(long)]
+        dry_run: bool,
+    },
     /// Add a tool to configuration and sync
     Add {
         /// Tool name to add
@@ -35,6 +85,9 @@ pub enum Commands {
         /// Skip syncing after adding
         #[arg(long)]
         no_sync: bool,
+        /// Print the planned actions without changing the config or filesystem
+        #[arg(long)]
+        dry_run: bool,
     },
     /// Remove symlink from a tool (use --all to remove all)
     Remove {
@@ -43,9 +96,53 @@ pub enum Commands {
         /// Remove from all destinations
         #[arg(long)]
         all: bool,
+        /// Remove even if other enabled tools share the same directory
+        #[arg(long)]
+        force: bool,
+        /// Print the planned actions without touching the filesystem
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Move a tool's existing skills into skills_source so it can be linked
+    Adopt {
+        /// Tool name to adopt
+        tool: String,
+        /// Move the existing directory to a timestamped backup instead
+        #[arg(long)]
+        backup: bool,
+        /// Skip syncing after adopting
+        #[arg(long)]
+        no_sync: bool,
+        /// Print the planned actions without touching the filesystem
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Run health checks on the config, sources, and destinations
+    Doctor {
+        /// Apply the fixes that cannot lose data (create missing sources, sync)
+        #[arg(long)]
+        fix: bool,
+    },
+    /// List the skills and commands in the configured sources
+    List {
+        /// Only list entries this tool receives
+        #[arg(long)]
+        tool: Option<String>,
+        /// Shorthand for --format json
+        #[arg(long)]
+        json: bool,
+    },
+    /// Check SKILL.md frontmatter and links in a skill or a directory of skills
+    Validate {
+        /// Skill directory, SKILL.md, or directory of skills (defaults to skills_source)
+        path: Option<PathBuf>,
     },
     /// Check symlink status
-    Status,
+    Status {
+        /// Exit with an error when an enabled destination is out of sync
+        #[arg(long)]
+        check: bool,
+    },
     /// Clone a remote skills repository
     Clone {
         /// Repository in owner/repo or full URL format
@@ -56,135 +153,421 @@ pub enum Commands {
         /// Skip syncing after clone
         #[arg(long)]
         no_sync: bool,
+        /// What to do when skills_source already exists
+        #[arg(long, value_enum)]
+        on_existing: Option<OnExisting>,
+        /// Back up an existing skills_source instead of deleting it
+        #[arg(long)]
+        backup: bool,
+        /// Answer yes to every confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Inspect tool registry files
+    Tools {
+        #[command(subcommand)]
+        command: ToolsCommand,
     },
     #[command(about = "Install a skill from an explicit reference")]
     Install {
         #[arg(
-            help = "Skill reference (HTTPS skills.sh URL, GitHub tree URL, or owner/repo/skill)"
+            help = "Skill reference (HTTPS skills.sh URL, tree URL, owner/repo/skill[@ref], local path, or .tar.gz/.zip archive)"
         )]
         reference: String,
+        #[arg(long = "ref", value_name = "REF")]
+        #[arg(help = "Install from this tag or commit instead of the tip of the branch")]
+        revision: Option<String>,
         #[arg(long)]
         #[arg(help = "Skip syncing after install")]
         no_sync: bool,
+        #[arg(long, conflicts_with = "skip_existing")]
+        #[arg(help = "Replace an installed skill with the same name without asking")]
+        force: bool,
+        #[arg(long)]
+        #[arg(help = "Keep an installed skill with the same name and skip it")]
+        skip_existing: bool,
+        #[arg(long, conflicts_with = "skills")]
+        #[arg(help = "Install every skill in the repository (reference is owner/repo)")]
+        all: bool,
+        #[arg(long = "skill", value_name = "NAME")]
+        #[arg(
+            help = "Install this skill from the repository (reference is owner/repo); repeatable"
+        )]
+        skills: Vec<String>,
+    },
+    /// Update skills installed with 'capsync install' from their recorded source
+    Update {
+        /// Installed skill slugs to update
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        slugs: Vec<String>,
+        /// Update every skill recorded in capsync.lock
+        #[arg(long)]
+        all: bool,
+        /// Replace skills whose files were edited since they were installed
+        #[arg(long)]
+        force: bool,
+        /// Skip syncing after updating
+        #[arg(long)]
+        no_sync: bool,
+        /// Show which skills have a newer upstream commit without replacing them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Remove a skill from skills_source and its per-skill links
+    Uninstall {
+        /// Installed skill slug to remove
+        slug: String,
+        /// Move the skill to a hidden backup in skills_source instead of deleting it
+        #[arg(long)]
+        backup: bool,
+        /// Print the planned actions without touching the filesystem
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Create a skill or command from a template
+    New {
+        #[command(subcommand)]
+        command: NewCommand,
     },
 }
 
-pub fn run() -> Result<()> {
-    let cli = Cli::parse();
+#[derive(Subcommand)]
+pub enum NewCommand {
+    /// Create `skills_source/<name>/SKILL.md`
+    Skill {
+        /// Skill name; lowercased and hyphenated to make the directory name
+        name: String,
+        /// Description for the frontmatter
+        #[arg(long)]
+        description: Option<String>,
+        /// Template from ~/.config/capsync/templates/skills/ (default: built-in)
+        #[arg(long)]
+        template: Option<String>,
+        /// Skip syncing after creating the skill
+        #[arg(long)]
+        no_sync: bool,
+    },
+    /// Create `commands_source/<name>.md`
+    Command {
+        /// Command name; lowercased and hyphenated to make the file name
+        name: String,
+        /// Description for the frontmatter
+        #[arg(long)]
+        description: Option<String>,
+        /// Template from ~/.config/capsync/templates/commands/ (default: built-in)
+        #[arg(long)]
+        template: Option<String>,
+        /// Skip syncing after creating the command
+        #[arg(long)]
+        no_sync: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ToolsCommand {
+    /// Check a registry file for duplicate names, non-relative paths and shared paths
+    Validate {
+        /// Registry file to check (e.g. ~/.config/capsync/tools.toml)
+        file: PathBuf,
+    },
+}
+
+/// Runs the command line and maps failures to the exit codes documented on
+/// [`CapsyncError::exit_code`].
+pub fn run() -> ExitCode {
+    match execute(Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {:?}", e);
+            ExitCode::from(CapsyncError::find(&e).map_or(1, CapsyncError::exit_code))
+        }
+    }
+}
+
+fn execute(cli: Cli) -> Result<()> {
+    if let Some(home) = cli.home {
+        paths::set_home_override(home);
+    }
+    if let Some(config) = cli.config {
+        paths::set_config_override(config);
+    }
 
     match cli.command {
-        Commands::Init => init_config(),
-        Commands::Config => show_config(),
-        Commands::DetectTools => detect_tools(),
-        Commands::Sync => sync_all(),
-        Commands::Add { tool, no_sync } => add_tool(&tool, no_sync),
-        Commands::Remove { tool, all } => {
+        Commands::Init {
+            skills_source,
+            commands_source,
+            enable,
+            detect,
+            force,
+        } => init_config(InitOptions {
+            skills_source,
+            commands_source,
+            enable,
+            detect,
+            force,
+        }),
+        Commands::Config => show_config(cli.format),
+        Commands::DetectTools => detect_tools(cli.format),
+        Commands::Sync { dry_run } => {
+            if dry_run {
+                plan_sync()
+            } else {
+                sync_all()
+            }
+        }
+        Commands::Add {
+            tool,
+            no_sync,
+            dry_run,
+        } => add_tool(&tool, no_sync, dry_run),
+        Commands::Remove {
+            tool,
+            all,
+            force,
+            dry_run,
+        } => {
             if all {
-                remove_all()
+                remove_all(dry_run)
             } else if let Some(tool_name) = tool {
-                remove_tool(&tool_name)
+                remove_tool(&tool_name, force, dry_run)
             } else {
                 Err(anyhow!("Either provide a tool name or use --all flag"))
             }
         }
+        Commands::Adopt {
+            tool,
+            backup,
+            no_sync,
+            dry_run,
+        } => adopt_tool(&tool, backup, no_sync, dry_run),
         Commands::Clone {
             repo,
             branch,
             no_sync,
-        } => clone_repo(&repo, branch, no_sync),
-        Commands::Install { reference, no_sync } => install_from_reference(&reference, no_sync),
-        Commands::Status => show_status(),
+            on_existing,
+            backup,
+            yes,
+        } => clone_repo(
+            CloneOptions {
+                repo,
+                branch,
+                on_existing,
+                backup,
+                yes,
+            },
+            no_sync,
+        ),
+        Commands::Install {
+            reference,
+            revision,
+            no_sync,
+            force,
+            skip_existing,
+            all,
+            skills,
+        } => {
+            let existing = if force {
+                ExistingSkill::Replace
+            } else if skip_existing {
+                ExistingSkill::Skip
+            } else {
+                ExistingSkill::Prompt
+            };
+            if all || !skills.is_empty() {
+                let skills = if all {
+                    SkillSet::All
+                } else {
+                    SkillSet::Named(skills)
+                };
+                install_from_repository(
+                    RepoInstallOptions {
+                        reference,
+                        skills,
+                        existing,
+                        revision,
+                    },
+                    no_sync,
+                )
+            } else {
+                install_from_reference(
+                    InstallOptions {
+                        reference,
+                        existing,
+                        revision,
+                    },
+                    no_sync,
+                )
+            }
+        }
+        Commands::Update {
+            slugs,
+            all,
+            force,
+            no_sync,
+            dry_run,
+        } => update_installed(
+            UpdateOptions {
+                slugs,
+                all,
+                force,
+                dry_run,
+            },
+            no_sync,
+        ),
+        Commands::Uninstall {
+            slug,
+            backup,
+            dry_run,
+        } => uninstall(UninstallOptions { slug, backup }, dry_run),
+        Commands::New { command } => match command {
+            NewCommand::Skill {
+                name,
+                description,
+                template,
+                no_sync,
+            } => create_from_template(
+                NewOptions {
+                    kind: SourceKind::Skills,
+                    name,
+                    description,
+                    template,
+                },
+                no_sync,
+            ),
+            NewCommand::Command {
+                name,
+                description,
+                template,
+                no_sync,
+            } => create_from_template(
+                NewOptions {
+                    kind: SourceKind::Commands,
+                    name,
+                    description,
+                    template,
+                },
+                no_sync,
+            ),
+        },
+        Commands::Tools { command } => match command {
+            ToolsCommand::Validate { file } => validate_tools_file(&file),
+        },
+        Commands::Doctor { fix } => run_doctor(fix, cli.format),
+        Commands::Status { check } => show_status(check, cli.format),
+        Commands::Validate { path } => validate_skills(path, cli.format),
+        Commands::List { tool, json } => {
+            let format = if json { OutputFormat::Json } else { cli.format };
+            list_sources(tool.as_deref(), format)
+        }
     }
 }
 
-fn init_config() -> Result<()> {
+/// Answers for `capsync init` given as flags instead of at the prompts.
+#[derive(Default)]
+struct InitOptions {
+    skills_source: Option<String>,
+    commands_source: Option<String>,
+    enable: Vec<String>,
+    detect: bool,
+    force: bool,
+}
+
+fn init_config(options: InitOptions) -> Result<()> {
     let config_path = config::get_config_path();
+    let interactive = options.skills_source.is_none();
+
+    if interactive && !io::stdin().is_terminal() {
+        return Err(CapsyncError::InputRequired {
+            question: "Enter your skills source directory:".to_string(),
+            flag: Some(
+                "--skills-source <path> (and optionally --commands-source, --enable <tool>, --detect, --force)"
+                    .to_string(),
+            ),
+        }
+        .into());
+    }
+
+    if config_path.exists() && !options.force {
+        if !interactive {
+            return Err(anyhow!(
+                "Configuration already exists at {}. Pass --force to overwrite it.",
+                config_path.display()
+            ));
+        }
 
-    if config_path.exists() {
         println!("Configuration already exists at: {}", config_path.display());
-        print!("Re-initialize? This will overwrite your current config. [y/N]: ");
-        io::stdout().flush()?;
-        let mut confirm = String::new();
-        io::stdin().read_line(&mut confirm)?;
-        if confirm.trim().to_lowercase() != "y" {
+        if !Terminal.confirm(
+            "Re-initialize? This will overwrite your current config.",
+            false,
+        )? {
             println!("Aborted.");
             return Ok(());
         }
     }
 
-    println!("Welcome to CapSync! Let's set up your configuration.\n");
-
-    let skills_source = loop {
-        print!("Enter your skills source directory: ");
-        io::stdout().flush()?;
-        let mut source_input = String::new();
-        io::stdin().read_line(&mut source_input)?;
-        let trimmed = source_input.trim();
-        if !trimmed.is_empty() {
-            let expanded =
-                shellexpand::full(trimmed).map_err(|e| anyhow!("Failed to expand path: {}", e))?;
-            break PathBuf::from(expanded.as_ref());
+    let skills_source = match &options.skills_source {
+        Some(source) => paths::expand(source)?,
+        None => {
+            println!("Welcome to CapSync! Let's set up your configuration.\n");
+            prompt_skills_source()?
         }
-        println!("Please enter a path.");
     };
 
-    let commands_source = {
-        let commands_dir = skills_source.join("commands");
-        if commands_dir.exists() && commands_dir.is_dir() {
-            println!("\nFound commands/ subdirectory in skills source.");
-            loop {
-                print!("Enable commands? [Y/n]: ");
-                io::stdout().flush()?;
-                let mut input = String::new();
-                io::stdin().read_line(&mut input)?;
-                let trimmed = input.trim().to_lowercase();
-                if trimmed.is_empty() || trimmed == "y" {
-                    break Some(commands_dir);
-                } else if trimmed == "n" {
-                    break None;
-                }
-                println!("Please enter Y or n.");
-            }
-        } else {
-            println!("\n(Optional) Enter commands source directory");
-            print!("(or press Enter to skip): ");
-            io::stdout().flush()?;
-            let mut input = String::new();
-            io::stdin().read_line(&mut input)?;
-            let trimmed = input.trim();
-            if trimmed.is_empty() {
-                None
-            } else {
-                let expanded = shellexpand::full(trimmed)
-                    .map_err(|e| anyhow!("Failed to expand path: {}", e))?;
-                Some(PathBuf::from(expanded.as_ref()))
-            }
-        }
+    let commands_source = match &options.commands_source {
+        Some(source) => Some(paths::expand(source)?),
+        None if interactive => prompt_commands_source(&skills_source)?,
+        None => None,
     };
 
-    println!("\nDetecting installed tools...");
-    let detected = ToolDetector::detect_all();
+    // Keep custom tools and the install host across re-initialization
+    let (custom_tools, default_host) = config::load_config()
+        .map(|existing| (existing.custom_tools, existing.default_host))
+        .unwrap_or_default();
+    let registry = ToolRegistry::load(&custom_tools)?;
 
-    let mut destinations = HashMap::new();
+    let unknown: Vec<_> = options
+        .enable
+        .iter()
+        .filter(|name| registry.get(name).is_none())
+        .map(String::as_str)
+        .collect();
+    if !unknown.is_empty() {
+        return Err(anyhow!(
+            "Unknown tool(s): {}. Run 'capsync detect-tools' to see detected tools, or define them under [custom_tools.<name>]",
+            unknown.join(", ")
+        ));
+    }
 
-    // Only add detected tools to config
-    for tool in all_tools() {
-        if detected.contains(&tool.name.to_string()) {
-            destinations.insert(
-                tool.name.to_string(),
-                DestinationConfig {
-                    enabled: true,
-                    skills_path: tool.skills_path.clone(),
-                    commands_path: tool.commands_path.clone(),
-                },
-            );
+    // Without explicit tool flags, enable whatever is detected
+    let detect = options.detect || (options.enable.is_empty() && interactive);
+    let detected = if detect {
+        println!("\nDetecting installed tools...");
+        ToolDetector::detect_in(&registry)
+    } else {
+        Vec::new()
+    };
+
+    let mut destinations = HashMap::new();
+    for tool in registry.all() {
+        if detected.contains(&tool.name) || options.enable.contains(&tool.name) {
+            destinations.insert(tool.name.clone(), DestinationConfig::for_tool(tool));
         }
     }
 
-    if detected.is_empty() {
-        println!("No tools detected. You can manually add tools to the config later.");
-    } else {
-        println!("Detected and enabled: {}", detected.join(", "));
+    if detect {
+        if detected.is_empty() {
+            println!("No tools detected. You can manually add tools to the config later.");
+        } else {
+            println!("Detected and enabled: {}", detected.join(", "));
+        }
+    }
+    if !options.enable.is_empty() {
+        println!("Enabled: {}", options.enable.join(", "));
+    }
+    if destinations.is_empty() && !interactive {
+        println!(
+            "No tools enabled. Pass --enable <tool> or --detect, or run 'capsync add <tool>' later."
+        );
     }
 
     // Create and save config
@@ -192,6 +575,8 @@ fn init_config() -> Result<()> {
         skills_source,
         commands_source,
         destinations,
+        custom_tools,
+        default_host,
     };
 
     config::save_config(&config).map_err(|e| {
@@ -211,10 +596,42 @@ fn init_config() -> Result<()> {
     Ok(())
 }
 
-fn show_config() -> Result<()> {
+fn prompt_skills_source() -> Result<PathBuf> {
+    loop {
+        let input = Terminal.ask("Enter your skills source directory:")?;
+        if !input.is_empty() {
+            return paths::expand(&input);
+        }
+        println!("Please enter a path.");
+    }
+}
+
+fn prompt_commands_source(skills_source: &Path) -> Result<Option<PathBuf>> {
+    let commands_dir = skills_source.join("commands");
+    if commands_dir.exists() && commands_dir.is_dir() {
+        println!("\nFound commands/ subdirectory in skills source.");
+        return Ok(Terminal
+            .confirm("Enable commands?", true)?
+            .then_some(commands_dir));
+    }
+
+    println!("\n(Optional) Enter commands source directory");
+    let input = Terminal.ask("(or press Enter to skip):")?;
+    if input.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(paths::expand(&input)?))
+    }
+}
+
+fn show_config(format: OutputFormat) -> Result<()> {
     let config = config::load_config()?;
     let config_path = config::get_config_path();
 
+    if format == OutputFormat::Json {
+        return print_json(&status::config_report(&config, &config_path));
+    }
+
     println!("Current Configuration:");
     println!("=====================");
     println!("Config file: {}", config_path.display());
@@ -246,11 +663,30 @@ fn show_config() -> Result<()> {
         }
     }
 
+    if let Some(default_host) = &config.default_host {
+        println!("\nInstall shorthand host: {}", default_host);
+    }
+
+    if !config.custom_tools.is_empty() {
+        println!("\nCustom tools:");
+        for (name, custom) in &config.custom_tools {
+            println!("  {}: {}", name, custom.skills_path.display());
+        }
+    }
+
     Ok(())
 }
 
-fn detect_tools() -> Result<()> {
-    let detected = ToolDetector::detect_all();
+fn detect_tools(format: OutputFormat) -> Result<()> {
+    let custom_tools = config::load_config()
+        .map(|config| config.custom_tools)
+        .unwrap_or_default();
+    let registry = ToolRegistry::load(&custom_tools)?;
+    let detected = ToolDetector::detect_with_evidence(&registry);
+
+    if format == OutputFormat::Json {
+        return print_json(&detected);
+    }
 
     println!("Detected Tools:");
     println!("===============");
@@ -259,7 +695,15 @@ fn detect_tools() -> Result<()> {
         println!("No supported agentic tools detected.");
     } else {
         for tool in &detected {
-            println!("{}", tool);
+            if tool.shares_skills_path_with.is_empty() {
+                println!("{}", tool.name);
+            } else {
+                println!(
+                    "{} (same skills directory as {})",
+                    tool.name,
+                    tool.shares_skills_path_with.join(", ")
+                );
+            }
         }
     }
 
@@ -283,27 +727,82 @@ fn sync_all() -> Result<()> {
     let result = SyncManager::sync_all(&config)?;
     result.print();
 
+    if !result.errors.is_empty() {
+        return Err(CapsyncError::SyncFailed {
+            failures: result.errors,
+        }
+        .into());
+    }
+
     Ok(())
 }
 
-fn remove_tool(tool: &str) -> Result<()> {
+fn plan_sync() -> Result<()> {
     let config = config::load_config()?;
-    SyncManager::remove(tool, &config)
+
+    println!("Planning sync (dry run)...");
+    println!("==========================");
+    println!("Skills source: {}", config.skills_source.display());
+
+    if let Some(commands_source) = &config.commands_source {
+        println!("Commands source: {}", commands_source.display());
+    }
+
+    SyncManager::plan_sync(&config)?.print();
+    println!("\nDry run: no changes were made.");
+
+    Ok(())
+}
+
+fn remove_tool(tool: &str, force: bool, dry_run: bool) -> Result<()> {
+    let config = config::load_config()?;
+
+    let sharing = SyncManager::tools_sharing_paths(tool, &config);
+    if !sharing.is_empty() {
+        if !force && !dry_run {
+            return Err(anyhow!(
+                "'{}' shares its directory with enabled tool(s): {}. Removing it would unlink them too. Disable them first or pass --force",
+                tool,
+                sharing.join(", ")
+            ));
+        }
+        eprintln!(
+            "Warning: {} also use this directory and will lose their links",
+            sharing.join(", ")
+        );
+    }
+
+    if dry_run {
+        SyncManager::plan_remove(tool, &config)?.print();
+        println!("\nDry run: no changes were made.");
+        return Ok(());
+    }
+
+    Ok(SyncManager::remove(tool, &config, &Terminal)?)
 }
 
-fn remove_all() -> Result<()> {
+fn remove_all(dry_run: bool) -> Result<()> {
     let config = config::load_config()?;
+
+    if dry_run {
+        SyncManager::plan_remove_all(&config).print();
+        println!("\nDry run: no changes were made.");
+        return Ok(());
+    }
+
     println!("Removing all symlinks...");
-    SyncManager::remove_all(&config)
+    Ok(SyncManager::remove_all(&config, &Terminal)?)
 }
 
-fn add_tool(tool_name: &str, no_sync: bool) -> Result<()> {
+fn add_tool(tool_name: &str, no_sync: bool, dry_run: bool) -> Result<()> {
     let mut config = config::load_config()?;
 
     // Validate tool exists
-    let tool = get_tool(tool_name).ok_or_else(|| {
+    let registry = config.tool_registry()?;
+    let tool = registry.get(tool_name).cloned().ok_or_else(|| {
         anyhow!(
-            "Tool '{}' does not exist or is unsupported in the current version",
+            "Tool '{}' does not exist or is unsupported in the current version. Define it under [custom_tools.{}] in the config to add it",
+            tool_name,
             tool_name
         )
     })?;
@@ -312,6 +811,11 @@ fn add_tool(tool_name: &str, no_sync: bool) -> Result<()> {
     if config.destinations.contains_key(tool_name) {
         println!("Tool '{}' is already in the configuration", tool_name);
         if !no_sync {
+            if dry_run {
+                SyncManager::plan_sync(&config)?.print();
+                println!("\nDry run: no changes were made.");
+                return Ok(());
+            }
             println!("Running sync...");
             return sync_all();
         }
@@ -319,18 +823,35 @@ fn add_tool(tool_name: &str, no_sync: bool) -> Result<()> {
     }
 
     // Add tool to config
-    config.destinations.insert(
-        tool_name.to_string(),
-        DestinationConfig {
-            enabled: true,
-            skills_path: tool.skills_path.clone(),
-            commands_path: tool.commands_path.clone(),
-        },
-    );
+    config
+        .destinations
+        .insert(tool_name.to_string(), DestinationConfig::for_tool(&tool));
+
+    if dry_run {
+        println!(
+            "Would add '{}' to configuration at {}",
+            tool_name,
+            config::get_config_path().display()
+        );
+        if !no_sync {
+            SyncManager::plan_sync(&config)?.print();
+        }
+        println!("\nDry run: no changes were made.");
+        return Ok(());
+    }
 
     config::save_config(&config)?;
     println!("Added '{}' to configuration", tool_name);
 
+    let sharing = SyncManager::tools_sharing_paths(tool_name, &config);
+    if !sharing.is_empty() {
+        println!(
+            "Note: '{}' uses the same directory as {}; it is synced once for all of them",
+            tool_name,
+            sharing.join(", ")
+        );
+    }
+
     if !no_sync {
         println!("Running sync...");
         sync_all()?;
@@ -339,27 +860,79 @@ fn add_tool(tool_name: &str, no_sync: bool) -> Result<()> {
     Ok(())
 }
 
-fn clone_repo(repo: &str, branch: Option<String>, no_sync: bool) -> Result<()> {
-    let config = match config::load_config() {
-        Ok(c) => c,
-        Err(e) => {
-            let config_path = config::get_config_path();
-            if !config_path.exists() {
-                println!("No configuration found. Running init first...");
-                init_config()?;
-                config::load_config()?
-            } else {
-                return Err(e).context("Failed to load config");
+fn adopt_tool(tool: &str, backup: bool, no_sync: bool, dry_run: bool) -> Result<()> {
+    let config = config::load_config()?;
+    let strategy = if backup {
+        AdoptStrategy::Backup
+    } else {
+        AdoptStrategy::MoveToSource
+    };
+
+    let plan = SyncManager::plan_adopt(tool, &config, strategy)?;
+
+    if dry_run {
+        plan.print();
+        println!("\nDry run: no changes were made.");
+        return Ok(());
+    }
+
+    let result = SyncManager::apply(&plan);
+    for entry in &plan.entries {
+        if !result.successful.contains(&entry.name) {
+            continue;
+        }
+
+        if !entry.has_changes() {
+            println!("Nothing to adopt at {}", entry.path.display());
+        }
+
+        for action in &entry.actions {
+            if let PlannedAction::Move { from, to } = action {
+                println!("Moved {} to {}", from.display(), to.display());
             }
         }
-    };
+    }
 
-    let options = CloneOptions {
-        repo: repo.to_string(),
-        branch,
-    };
+    if let Some((name, error)) = result.errors.into_iter().next() {
+        return Err(anyhow!("Failed to adopt {}: {}", name, error));
+    }
 
-    let result = clone_skills(&options, &config)?;
+    if !no_sync {
+        println!("\nRunning sync...");
+        sync_all()?;
+    }
+
+    Ok(())
+}
+
+fn validate_tools_file(file: &Path) -> Result<()> {
+    let content = std::fs::read_to_string(file)
+        .with_context(|| format!("Failed to read {}", file.display()))?;
+    let report = validate_registry(&content)?;
+
+    for warning in &report.warnings {
+        println!("warning: {}", warning);
+    }
+    for error in &report.errors {
+        println!("error: {}", error);
+    }
+
+    if !report.is_valid() {
+        return Err(anyhow!(
+            "{} has {} problem(s)",
+            file.display(),
+            report.errors.len()
+        ));
+    }
+
+    println!("{} is a valid tool registry", file.display());
+    Ok(())
+}
+
+fn clone_repo(options: CloneOptions, no_sync: bool) -> Result<()> {
+    let config = load_or_init_config()?;
+
+    let result = clone_skills(&options, &config, &Terminal, &Terminal)?;
 
     match result.action {
         CloneAction::Cloned => {
@@ -386,28 +959,31 @@ fn clone_repo(repo: &str, branch: Option<String>, no_sync: bool) -> Result<()> {
     Ok(())
 }
 
-fn install_from_reference(reference: &str, no_sync: bool) -> Result<()> {
-    let config = match config::load_config() {
-        Ok(c) => c,
-        Err(e) => {
-            let config_path = config::get_config_path();
-            if !config_path.exists() {
-                println!("No configuration found. Running init first...");
-                init_config()?;
-                config::load_config()?
-            } else {
-                return Err(e).context("Failed to load config");
-            }
+/// Loads the config, running `init` first when there is none yet.
+fn load_or_init_config() -> Result<Config> {
+    match config::load_config() {
+        Ok(c) => Ok(c),
+        Err(CapsyncError::ConfigNotFound { .. }) => {
+            println!("No configuration found. Running init first...");
+            init_config(InitOptions::default())?;
+            Ok(config::load_config()?)
         }
-    };
+        Err(e) => Err(e).context("Failed to load config"),
+    }
+}
 
-    let options = InstallOptions {
-        reference: reference.to_string(),
-    };
+fn install_from_reference(options: InstallOptions, no_sync: bool) -> Result<()> {
+    let config = load_or_init_config()?;
 
-    let result = install_skill(&options, &config)?;
+    let result = install_skill(&options, &config, &Terminal, &Terminal)?;
 
-    if result.replaced_existing {
+    if result.skipped_existing {
+        println!(
+            "\nSkill '{}' is already installed at {}; skipped (--skip-existing)",
+            result.skill_slug,
+            result.installed_path.display()
+        );
+    } else if result.replaced_existing {
         println!(
             "\nReplaced installed skill '{}' at {}",
             result.skill_slug,
@@ -431,28 +1007,260 @@ fn install_from_reference(reference: &str, no_sync: bool) -> Result<()> {
     Ok(())
 }
 
-fn show_status() -> Result<()> {
+fn install_from_repository(options: RepoInstallOptions, no_sync: bool) -> Result<()> {
+    let config = load_or_init_config()?;
+
+    let result = install_repository(&options, &config, &Terminal, &Terminal)?;
+
+    let changed = result
+        .installed()
+        .filter(|installed| !installed.skipped_existing)
+        .count();
+    let skipped = result.installed().count() - changed;
+    let failures = result.failures();
+
+    println!();
+    result.report(&Terminal);
+    println!(
+        "\nInstalled {} skill(s), skipped {}, failed {}",
+        changed,
+        skipped,
+        failures.len()
+    );
+
+    if changed > 0 && !no_sync {
+        println!("\nRunning sync...");
+        sync_all()?;
+    } else if changed > 0 {
+        println!("\nSkipped sync (--no-sync passed). Run 'capsync sync' manually to sync.");
+    }
+
+    if !failures.is_empty() {
+        return Err(CapsyncError::InstallFailed { failures }.into());
+    }
+
+    Ok(())
+}
+
+fn update_installed(options: UpdateOptions, no_sync: bool) -> Result<()> {
     let config = config::load_config()?;
+    let result = update_skills(&options, &config, &Terminal)?;
 
+    if result.skills.is_empty() {
+        println!(
+            "No installed skills recorded in {}",
+            Lockfile::path(&config.skills_source).display()
+        );
+        return Ok(());
+    }
+
+    println!();
+    result.report(&Terminal);
+
+    let failures = result.failures();
+    if !failures.is_empty() {
+        return Err(CapsyncError::UpdateFailed { failures }.into());
+    }
+
+    let updated = result
+        .skills
+        .iter()
+        .any(|skill| matches!(skill.status, UpdateStatus::Updated { .. }));
+    if options.dry_run {
+        println!("\nDry run: no skills were replaced.");
+    } else if updated && !no_sync {
+        println!("\nRunning sync...");
+        sync_all()?;
+    } else if updated {
+        println!("\nSkipped sync (--no-sync passed). Run 'capsync sync' manually to sync.");
+    }
+
+    Ok(())
+}
+
+fn uninstall(options: UninstallOptions, dry_run: bool) -> Result<()> {
+    let config = config::load_config()?;
+
+    if dry_run {
+        plan_uninstall(&options, &config)?.print();
+        println!("\nDry run: no changes were made.");
+        return Ok(());
+    }
+
+    let result = uninstall_skill(&options, &config, &Terminal)?;
+
+    for link in &result.unlinked {
+        println!("Removed link {}", link.display());
+    }
+
+    match &result.backup_path {
+        Some(backup_path) => println!("Moved skill '{}' to {}", result.slug, backup_path.display()),
+        None => println!("Uninstalled skill '{}'", result.slug),
+    }
+
+    Ok(())
+}
+
+fn create_from_template(options: NewOptions, no_sync: bool) -> Result<()> {
+    let config = config::load_config()?;
+    let result = scaffold::create(&options, &config, &paths::templates_dir())?;
+
+    let label = match options.kind {
+        SourceKind::Skills => "skill",
+        SourceKind::Commands => "command",
+    };
+    println!(
+        "Created {} '{}' at {} from the '{}' template",
+        label,
+        result.slug,
+        result.path.display(),
+        result.template
+    );
+
+    if !no_sync {
+        println!("\nRunning sync...");
+        sync_all()?;
+    } else {
+        println!("\nSkipped sync (--no-sync passed). Run 'capsync sync' manually to sync.");
+    }
+
+    Ok(())
+}
+
+fn list_sources(tool: Option<&str>, format: OutputFormat) -> Result<()> {
+    let config = config::load_config()?;
+    let inventory = list::inventory(&config, tool)?;
+
+    if format == OutputFormat::Json {
+        return print_json(&inventory);
+    }
+
+    println!(
+        "Skills in {} ({}):",
+        inventory.skills_source.display(),
+        inventory.skills.len()
+    );
+    if inventory.skills.is_empty() {
+        println!("  (none)");
+    }
+    for skill in &inventory.skills {
+        print_inventory_item(skill);
+    }
+
+    if let Some(commands_source) = &inventory.commands_source {
+        println!(
+            "\nCommands in {} ({}):",
+            commands_source.display(),
+            inventory.commands.len()
+        );
+        if inventory.commands.is_empty() {
+            println!("  (none)");
+        }
+        for command in &inventory.commands {
+            print_inventory_item(command);
+        }
+    }
+
+    Ok(())
+}
+
+fn print_inventory_item(item: &list::InventoryItem) {
+    match (&item.name, &item.description) {
+        (Some(name), Some(description)) if name != &item.slug => {
+            println!("  {} ({}): {}", item.slug, name, description)
+        }
+        (_, Some(description)) => println!("  {}: {}", item.slug, description),
+        (Some(name), None) if name != &item.slug => println!("  {} ({})", item.slug, name),
+        _ => println!("  {}", item.slug),
+    }
+
+    if let Some(locked) = &item.installed_from {
+        println!(
+            "      installed from {} at {}",
+            locked.reference,
+            lockfile::short_version(locked.source_version())
+        );
+    }
+
+    if item.tools.is_empty() {
+        println!("      tools: (none enabled)");
+    } else {
+        println!("      tools: {}", item.tools.join(", "));
+    }
+}
+
+fn validate_skills(path: Option<PathBuf>, format: OutputFormat) -> Result<()> {
+    let path = match path {
+        Some(path) => path,
+        None => config::load_config()?.skills_source,
+    };
+    let report = validate::validate_path(&path)?;
+
+    if format == OutputFormat::Json {
+        print_json(&report)?;
+    } else {
+        for problem in &report.problems {
+            println!("{}", problem);
+        }
+        if !report.problems.is_empty() {
+            println!();
+        }
+        println!(
+            "Checked {} skill(s): {} error(s), {} warning(s)",
+            report.skills_checked,
+            report.count(Severity::Error),
+            report.count(Severity::Warning)
+        );
+    }
+
+    let errors = report.count(Severity::Error);
+    if errors > 0 {
+        return Err(CapsyncError::ValidationFailed { count: errors }.into());
+    }
+
+    Ok(())
+}
+
+fn show_status(check: bool, format: OutputFormat) -> Result<()> {
+    let config = config::load_config()?;
+    let report = status::status_report(&config, &config::get_config_path());
+
+    if format == OutputFormat::Json {
+        print_json(&report)?;
+    } else {
+        print_status_report(&report);
+    }
+
+    if check && !report.in_sync {
+        return Err(CapsyncError::OutOfSync {
+            count: report.out_of_sync().count(),
+        }
+        .into());
+    }
+
+    Ok(())
+}
+
+fn print_status_report(report: &status::StatusReport) {
     println!("Status:");
     println!("=======");
 
-    if config.skills_source.exists() {
-        println!("Skills source: {}", config.skills_source.display());
+    if report.skills_source.exists {
+        println!("Skills source: {}", report.skills_source.path.display());
     } else {
         println!(
             "Skills source: {} (does not exist)",
-            config.skills_source.display()
+            report.skills_source.path.display()
         );
     }
 
-    if let Some(commands_source) = &config.commands_source {
-        if commands_source.exists() {
-            println!("Commands source: {}", commands_source.display());
+    if let Some(commands_source) = &report.commands_source {
+        if commands_source.exists {
+            println!("Commands source: {}", commands_source.path.display());
         } else {
             println!(
                 "Commands source: {} (does not exist)",
-                commands_source.display()
+                commands_source.path.display()
             );
         }
     } else {
@@ -460,72 +1268,172 @@ fn show_status() -> Result<()> {
     }
 
     println!("\nDestinations:");
-    for (name, dest) in &config.destinations {
-        let path = &dest.skills_path;
-        if path.is_symlink() {
-            match path.read_link() {
-                Ok(target) => {
-                    if target.exists() {
-                        println!(
-                            "  {}: {} (symlink -> {})",
-                            name,
-                            path.display(),
-                            target.display()
-                        );
-                    } else {
-                        println!(
-                            "  {}: {} (broken symlink -> {})",
-                            name,
-                            path.display(),
-                            target.display()
-                        );
-                    }
-                }
-                Err(_) => {
-                    println!("  {}: {} (cannot read symlink)", name, path.display());
-                }
-            }
-        } else if path.exists() {
-            println!("  {}: {} (exists, not a symlink)", name, path.display());
-        } else {
-            println!("  {}: {} - (not synced)", name, path.display());
-        }
-
-        if let Some(commands_path) = &dest.commands_path {
-            if commands_path.is_symlink() {
-                match commands_path.read_link() {
-                    Ok(target) => {
-                        if target.exists() {
-                            println!(
-                                "    commands: {} (symlink -> {})",
-                                commands_path.display(),
-                                target.display()
-                            );
-                        } else {
-                            println!(
-                                "    commands: {} (broken symlink -> {})",
-                                commands_path.display(),
-                                target.display()
-                            );
-                        }
-                    }
-                    Err(_) => {
-                        println!(
-                            "    commands: {} (cannot read symlink)",
-                            commands_path.display()
-                        );
-                    }
-                }
-            } else if commands_path.exists() {
+    for dest in &report.destinations {
+        print_path_status(&format!("  {}", dest.name), &dest.skills, dest.link_mode);
+
+        if let Some(commands) = &dest.commands {
+            print_path_status("    commands", commands, dest.link_mode);
+        }
+    }
+
+    let out_of_sync: Vec<_> = report
+        .out_of_sync()
+        .map(|dest| dest.name.as_str())
+        .collect();
+    if out_of_sync.is_empty() {
+        println!("\nAll enabled destinations are in sync.");
+    } else {
+        println!("\nOut of sync: {}", out_of_sync.join(", "));
+    }
+}
+
+fn print_path_status(label: &str, status: &PathStatus, link_mode: LinkMode) {
+    let path = status.path.display();
+    let mode_name = if link_mode == LinkMode::Hardlink {
+        "hardlink"
+    } else {
+        "copy"
+    };
+
+    match &status.state {
+        LinkState::Linked { target } => {
+            println!("{}: {} (symlink -> {})", label, path, target.display());
+        }
+        LinkState::LinkedElsewhere { target } => println!(
+            "{}: {} (symlink -> {}, not the configured source)",
+            label,
+            path,
+            target.display()
+        ),
+        LinkState::Broken { target } => {
+            println!(
+                "{}: {} (broken symlink -> {})",
+                label,
+                path,
+                target.display()
+            );
+        }
+        LinkState::Unreadable { .. } => println!("{}: {} (cannot read symlink)", label, path),
+        LinkState::ForeignDirectory | LinkState::ForeignFile => {
+            println!("{}: {} (exists, not a symlink)", label, path);
+        }
+        LinkState::Missing => println!("{}: {} - (not synced)", label, path),
+        LinkState::WholeDirectoryLink { .. } if link_mode == LinkMode::PerSkill => println!(
+            "{}: {} (whole-directory symlink, run 'capsync sync' to switch to per-skill links)",
+            label, path
+        ),
+        LinkState::WholeDirectoryLink { .. } => println!(
+            "{}: {} (symlink, run 'capsync sync' to switch to {} mode)",
+            label, path, mode_name
+        ),
+        LinkState::NoSource => {
+            println!("{}: {} - (no commands_source configured)", label, path);
+        }
+        LinkState::PerSkill(per_skill) => {
+            println!(
+                "{}: {} (per-skill: {} linked, {} missing, {} conflicting, {} stale)",
+                label,
+                path,
+                per_skill.linked.len(),
+                per_skill.missing.len(),
+                per_skill.conflicts.len(),
+                per_skill.stale.len()
+            );
+            if !per_skill.conflicts.is_empty() {
                 println!(
-                    "    commands: {} (exists, not a symlink)",
-                    commands_path.display()
+                    "      not managed by CapSync: {}",
+                    per_skill.conflicts.join(", ")
                 );
+            }
+        }
+        LinkState::Mirror(mirror) => {
+            if mirror.is_in_sync() {
+                println!("{}: {} ({}, in sync)", label, path, mode_name);
             } else {
-                println!("    commands: {} - (not synced)", commands_path.display());
+                println!(
+                    "{}: {} ({}, drifted: {} changed, {} missing, {} stale)",
+                    label,
+                    path,
+                    mode_name,
+                    mirror.changed.len(),
+                    mirror.missing.len(),
+                    mirror.stale.len()
+                );
+            }
+            if !mirror.conflicts.is_empty() {
+                println!(
+                    "      not managed by CapSync: {}",
+                    mirror.conflicts.join(", ")
+                );
+            }
+        }
+        LinkState::Error { error } => {
+            println!("{}: {} (cannot inspect: {})", label, path, error);
+        }
+    }
+}
+
+fn run_doctor(fix: bool, format: OutputFormat) -> Result<()> {
+    let config_path = config::get_config_path();
+    let mut findings = doctor::diagnose(&config_path);
+
+    if fix && findings.iter().any(|finding| finding.safe_fix.is_some()) {
+        let config = config::load_config_from(&config_path)?;
+        let sync_result = doctor::apply_safe_fixes(&findings, &config)?;
+        if format == OutputFormat::Text {
+            println!("Applying safe fixes...");
+            if let Some(result) = sync_result {
+                result.print();
             }
+            println!();
         }
+        findings = doctor::diagnose(&config_path);
     }
 
+    if format == OutputFormat::Json {
+        print_json(&findings)?;
+    } else {
+        print_findings(&findings, fix);
+    }
+
+    let errors = findings
+        .iter()
+        .filter(|finding| finding.severity == Severity::Error)
+        .count();
+    if errors > 0 {
+        return Err(CapsyncError::ChecksFailed { count: errors }.into());
+    }
+
+    Ok(())
+}
+
+fn print_findings(findings: &[Finding], fixed: bool) {
+    println!("Doctor:");
+    println!("=======");
+
+    if findings.is_empty() {
+        println!("No problems found.");
+        return;
+    }
+
+    for finding in findings {
+        let severity = match finding.severity {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        println!("[{}] {}: {}", severity, finding.check, finding.message);
+        if let Some(suggestion) = &finding.suggestion {
+            println!("    fix: {}", suggestion);
+        }
+    }
+
+    if !fixed && findings.iter().any(|finding| finding.safe_fix.is_some()) {
+        println!("\nRun 'capsync doctor --fix' to apply the safe fixes.");
+    }
+}
+
+fn print_json<T: Serialize>(value: &T) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
     Ok(())
 }
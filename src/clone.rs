@@ -1,10 +1,11 @@
 pub use crate::git::{clone_to_path, get_remote_default_branch};
 
 use crate::config::Config;
+use crate::error::CapsyncError;
 use crate::git::build_fetch_options;
+use crate::ui::{Prompter, Reporter};
 use anyhow::{Context, Result, anyhow};
 use git2::Repository;
-use std::io::{self, Write};
 use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 
@@ -20,9 +21,27 @@ pub enum CloneAction {
     Overridden,
 }
 
+/// What `clone` does when `skills_source` already exists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum OnExisting {
+    /// Pull the latest changes into the existing clone of the same repository.
+    Update,
+    /// Replace the existing directory with a fresh clone.
+    Override,
+    /// Leave the existing directory alone and stop.
+    Abort,
+}
+
+#[derive(Default)]
 pub struct CloneOptions {
     pub repo: String,
     pub branch: Option<String>,
+    /// Answers the update-or-override question. `None` asks.
+    pub on_existing: Option<OnExisting>,
+    /// Move an existing `skills_source` to a backup instead of deleting it.
+    pub backup: bool,
+    /// Answer yes to every confirmation.
+    pub yes: bool,
 }
 
 pub fn normalize_repo_identity(input: &str) -> Option<String> {
@@ -186,9 +205,7 @@ pub fn update_existing(path: &Path) -> Result<()> {
     let repository = Repository::open(path).context("Failed to open existing repository")?;
 
     if has_unpushed_changes(path) {
-        return Err(anyhow!(
-            "Cannot update: working tree has uncommitted or unpushed changes. Commit or stash them first."
-        ));
+        return Err(CapsyncError::UncommittedChanges(path.to_path_buf()).into());
     }
 
     let mut fetch_options = build_fetch_options();
@@ -264,7 +281,12 @@ fn current_branch_name(repository: &Repository) -> Result<String> {
         .map(str::to_string)
 }
 
-pub fn clone_skills(options: &CloneOptions, config: &Config) -> Result<CloneResult> {
+pub fn clone_skills(
+    options: &CloneOptions,
+    config: &Config,
+    prompter: &dyn Prompter,
+    reporter: &dyn Reporter,
+) -> Result<CloneResult, CapsyncError> {
     let url = parse_repo_url(&options.repo)?;
 
     let source = &config.skills_source;
@@ -273,143 +295,121 @@ pub fn clone_skills(options: &CloneOptions, config: &Config) -> Result<CloneResu
     let requested_branch = if let Some(branch_name) = &options.branch {
         branch_name.clone()
     } else {
-        println!("Fetching remote branch info...");
+        reporter.info("Fetching remote branch info...");
         get_remote_default_branch(&url)?
     };
 
-    println!("Using branch: {}", requested_branch);
+    reporter.info(&format!("Using branch: {}", requested_branch));
 
     let (action, backup_path) = if source_exists {
         let repository = open_repository(source)?;
+        let yes = options.yes.then_some(true);
 
-        if repository.is_some() {
-            let current_remote = get_remote_url(source)?;
-            println!("\nSkills source already exists.");
-
-            if let Some(remote_url) = current_remote.as_ref() {
-                let is_same_repo = match (
-                    normalize_repo_identity(remote_url),
-                    normalize_repo_identity(&url),
-                ) {
-                    (Some(current_repo), Some(requested_repo)) => current_repo == requested_repo,
-                    _ => remote_url == &url,
-                };
-
-                if is_same_repo {
-                    loop {
-                        print!("Update (git pull) or Override (download new)? [U/o]: ");
-                        io::stdout().flush()?;
-                        let mut input = String::new();
-                        io::stdin().read_line(&mut input)?;
-                        let input = input.trim().to_lowercase();
-
-                        if input.is_empty() || input == "u" {
-                            let existing_repository = Repository::open(source)
-                                .context("Failed to open existing repository")?;
-                            let current_branch = current_branch_name(&existing_repository)?;
-
-                            if current_branch != requested_branch {
-                                println!(
-                                    "\nRequested branch '{}' differs from current local branch '{}'.",
-                                    requested_branch, current_branch
-                                );
-
-                                loop {
-                                    print!(
-                                        "Re-clone the requested branch instead of updating in place? [y/N]: "
-                                    );
-                                    io::stdout().flush()?;
-                                    let mut branch_input = String::new();
-                                    io::stdin().read_line(&mut branch_input)?;
-                                    let branch_input = branch_input.trim().to_lowercase();
-
-                                    if branch_input == "y" {
-                                        break;
-                                    } else if branch_input.is_empty() || branch_input == "n" {
-                                        return Err(anyhow!("Aborted."));
-                                    }
-                                    println!("Please enter y or n.");
-                                }
-
-                                break;
-                            }
-
-                            update_existing(source)?;
-                            return Ok(CloneResult {
-                                action: CloneAction::Updated,
-                                backup_path: None,
-                            });
-                        } else if input == "o" {
-                            break;
-                        }
-                        println!("Please enter U or o.");
-                    }
-                } else {
-                    loop {
-                        print!("Override with different repository? [y/N]: ");
-                        io::stdout().flush()?;
-                        let mut input = String::new();
-                        io::stdin().read_line(&mut input)?;
-                        let input = input.trim().to_lowercase();
-
-                        if input == "y" {
-                            break;
-                        } else if input.is_empty() || input == "n" {
-                            return Err(anyhow!("Aborted."));
-                        }
-                        println!("Please enter y or n.");
-                    }
+        reporter.info("\nSkills source already exists.");
+
+        let current_remote = match repository {
+            Some(_) => get_remote_url(source)?,
+            None => None,
+        };
+        let is_same_repo = current_remote.as_ref().is_some_and(|remote_url| {
+            match (
+                normalize_repo_identity(remote_url),
+                normalize_repo_identity(&url),
+            ) {
+                (Some(current_repo), Some(requested_repo)) => current_repo == requested_repo,
+                _ => remote_url == &url,
+            }
+        });
+
+        if is_same_repo {
+            let choice = match options.on_existing {
+                Some(choice) => choice,
+                None => {
+                    let choices = [OnExisting::Update, OnExisting::Override];
+                    let index = prompter
+                        .choose(
+                            "Update (git pull) or Override (download new)?",
+                            &["update", "override"],
+                            0,
+                        )
+                        .map_err(|e| {
+                            CapsyncError::from(e).with_flag("--on-existing update|override|abort")
+                        })?;
+                    choices[index]
                 }
-            } else {
-                loop {
-                    print!("Skills source exists but has no origin remote. Override? [y/N]: ");
-                    io::stdout().flush()?;
-                    let mut input = String::new();
-                    io::stdin().read_line(&mut input)?;
-                    let input = input.trim().to_lowercase();
-
-                    if input == "y" {
-                        break;
-                    } else if input.is_empty() || input == "n" {
-                        return Err(anyhow!("Aborted."));
+            };
+
+            match choice {
+                OnExisting::Abort => return Err(CapsyncError::Aborted),
+                OnExisting::Override => {}
+                OnExisting::Update => {
+                    let existing_repository =
+                        Repository::open(source).context("Failed to open existing repository")?;
+                    let current_branch = current_branch_name(&existing_repository)?;
+
+                    if current_branch == requested_branch {
+                        update_existing(source)?;
+                        return Ok(CloneResult {
+                            action: CloneAction::Updated,
+                            backup_path: None,
+                        });
+                    }
+
+                    reporter.info(&format!(
+                        "\nRequested branch '{}' differs from current local branch '{}'.",
+                        requested_branch, current_branch
+                    ));
+                    if !confirm(
+                        prompter,
+                        "Re-clone the requested branch instead of updating in place?",
+                        yes,
+                        "--yes",
+                    )? {
+                        return Err(CapsyncError::Aborted);
                     }
-                    println!("Please enter y or n.");
                 }
             }
         } else {
-            loop {
-                print!("Skills source exists but is not a git repository. Override? [y/N]: ");
-                io::stdout().flush()?;
-                let mut input = String::new();
-                io::stdin().read_line(&mut input)?;
-                let input = input.trim().to_lowercase();
-
-                if input == "y" {
-                    break;
-                } else if input.is_empty() || input == "n" {
-                    return Err(anyhow!("Aborted."));
+            let question = match (&repository, &current_remote) {
+                (Some(_), Some(_)) => "Override with different repository?",
+                (Some(_), None) => "Skills source exists but has no origin remote. Override?",
+                (None, _) => "Skills source exists but is not a git repository. Override?",
+            };
+
+            let answer = match options.on_existing {
+                Some(OnExisting::Override) => Some(true),
+                Some(OnExisting::Abort) => Some(false),
+                Some(OnExisting::Update) => {
+                    return Err(anyhow!(
+                        "Cannot update {}: it is not a clone of {}. Use --on-existing override to replace it.",
+                        source.display(),
+                        options.repo
+                    )
+                    .into());
                 }
-                println!("Please enter y or n.");
+                None => yes,
+            };
+
+            if !confirm(
+                prompter,
+                question,
+                answer,
+                "--on-existing override or --yes",
+            )? {
+                return Err(CapsyncError::Aborted);
             }
         }
 
-        if has_unpushed_changes(source) {
-            println!("\nWARNING: Local skills_source has uncommitted or unpushed changes.");
-            loop {
-                print!("Backup and override? [y/N]: ");
-                io::stdout().flush()?;
-                let mut input = String::new();
-                io::stdin().read_line(&mut input)?;
-                let input = input.trim().to_lowercase();
-
-                if input == "y" {
-                    let backup = backup_existing(source)?;
-                    break (CloneAction::Overridden, Some(backup));
-                } else if input.is_empty() || input == "n" {
-                    return Err(anyhow!("Aborted."));
-                }
-                println!("Please enter y or n.");
+        if has_unpushed_changes(source) && !options.backup {
+            reporter.warn("Local skills_source has uncommitted or unpushed changes.");
+            if !confirm(prompter, "Backup and override?", yes, "--backup or --yes")? {
+                return Err(CapsyncError::Aborted);
             }
+            let backup = backup_existing(source)?;
+            (CloneAction::Overridden, Some(backup))
+        } else if options.backup {
+            let backup = backup_existing(source)?;
+            (CloneAction::Overridden, Some(backup))
         } else {
             std::fs::remove_dir_all(source).context("Failed to remove existing skills_source")?;
             (CloneAction::Overridden, None)
@@ -421,16 +421,32 @@ pub fn clone_skills(options: &CloneOptions, config: &Config) -> Result<CloneResu
         (CloneAction::Cloned, None)
     };
 
-    println!("Cloning into {}...", source.display());
+    reporter.info(&format!("Cloning into {}...", source.display()));
     clone_to_path(&url, &requested_branch, source)?;
 
-    println!(
+    reporter.info(&format!(
         "Successfully cloned {} (branch: {})",
         options.repo, requested_branch
-    );
+    ));
 
     Ok(CloneResult {
         action,
         backup_path,
     })
 }
+
+/// Asks a yes/no question that defaults to no, unless `answer` already
+/// settles it. If the prompter cannot ask, the error names the flag to pass.
+fn confirm(
+    prompter: &dyn Prompter,
+    question: &str,
+    answer: Option<bool>,
+    flag: &str,
+) -> Result<bool, CapsyncError> {
+    match answer {
+        Some(answer) => Ok(answer),
+        None => prompter
+            .confirm(question, false)
+            .map_err(|e| CapsyncError::from(e).with_flag(flag)),
+    }
+}
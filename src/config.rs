@@ -1,8 +1,10 @@
-use crate::tools::all_tools;
+use crate::error::CapsyncError;
+use crate::paths;
+use crate::tools::{Tool, ToolRegistry};
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Config {
@@ -11,6 +13,12 @@ pub struct Config {
     #[serde(default)]
     pub commands_source: Option<PathBuf>,
     pub destinations: HashMap<String, DestinationConfig>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub custom_tools: BTreeMap<String, CustomToolConfig>,
+    /// Host that `capsync install owner/repo/...` shorthand expands to, such as
+    /// `gitlab.example.com`. Defaults to `github.com`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_host: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -20,19 +28,77 @@ pub struct DestinationConfig {
     pub skills_path: PathBuf,
     #[serde(default)]
     pub commands_path: Option<PathBuf>,
+    #[serde(default, skip_serializing_if = "LinkMode::is_symlink")]
+    pub link_mode: LinkMode,
+}
+
+/// An in-house tool that is not in the built-in registry. Paths may start with `~`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CustomToolConfig {
+    /// The tool counts as installed when this path exists.
+    pub detect_path: PathBuf,
+    pub skills_path: PathBuf,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commands_path: Option<PathBuf>,
+}
+
+/// How a destination is populated from the configured sources.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LinkMode {
+    /// Replace the destination with a single symlink to the source directory.
+    #[default]
+    Symlink,
+    /// Keep the destination directory and link each source entry into it.
+    PerSkill,
+    /// Copy the source tree into the destination for tools that do not
+    /// follow symlinks.
+    Copy,
+    /// Like `Copy`, but hard-link each file instead of duplicating it.
+    Hardlink,
+}
+
+impl DestinationConfig {
+    /// An enabled destination using the tool's registry paths.
+    pub fn for_tool(tool: &Tool) -> Self {
+        Self {
+            enabled: true,
+            skills_path: tool.skills_path.clone(),
+            commands_path: tool.commands_path.clone(),
+            link_mode: LinkMode::default(),
+        }
+    }
+}
+
+impl LinkMode {
+    pub fn is_symlink(&self) -> bool {
+        *self == LinkMode::Symlink
+    }
+
+    pub fn is_mirror(&self) -> bool {
+        matches!(self, LinkMode::Copy | LinkMode::Hardlink)
+    }
 }
 
 impl Default for Config {
     fn default() -> Self {
+        Self::for_home(&paths::home_dir())
+    }
+}
+
+impl Config {
+    /// An empty config listing every built-in tool, disabled, with paths under `home`.
+    pub fn for_home(home: &Path) -> Self {
         let mut destinations = HashMap::new();
 
-        for tool in all_tools() {
+        for tool in ToolRegistry::builtin_in(home).all() {
             destinations.insert(
                 tool.name.to_string(),
                 DestinationConfig {
                     enabled: false, // Default to disabled, let user enable what they want
                     skills_path: tool.skills_path.clone(),
                     commands_path: tool.commands_path.clone(),
+                    link_mode: LinkMode::default(),
                 },
             );
         }
@@ -41,11 +107,18 @@ impl Default for Config {
             skills_source: PathBuf::new(),
             commands_source: None,
             destinations,
+            custom_tools: BTreeMap::new(),
+            default_host: None,
         }
     }
-}
 
-impl Config {
+    /// The host shorthand install references expand to.
+    pub fn install_host(&self) -> &str {
+        self.default_host
+            .as_deref()
+            .unwrap_or(crate::install::DEFAULT_HOST)
+    }
+
     pub fn has_commands(&self) -> bool {
         self.commands_source
             .as_ref()
@@ -53,26 +126,71 @@ impl Config {
             .unwrap_or(false)
     }
 
-    pub fn validate(&self) -> Result<()> {
+    /// Built-in tools, the user registry file, and the custom tools defined in this config.
+    pub fn tool_registry(&self) -> Result<ToolRegistry> {
+        ToolRegistry::load(&self.custom_tools)
+    }
+
+    pub fn validate(&self) -> Result<(), CapsyncError> {
         if self.skills_source.as_os_str().is_empty() {
-            return Err(anyhow::anyhow!(
-                "skills_source is not set. Run 'capsync init' to configure."
+            return Err(CapsyncError::ConfigInvalid(
+                "skills_source is not set. Run 'capsync init' to configure.".to_string(),
             ));
         }
+
+        for (name, custom) in &self.custom_tools {
+            if name.trim().is_empty() {
+                return Err(CapsyncError::ConfigInvalid(
+                    "custom_tools entries need a non-empty name".to_string(),
+                ));
+            }
+            if custom.skills_path.as_os_str().is_empty() {
+                return Err(CapsyncError::ConfigInvalid(format!(
+                    "custom_tools.{} is missing skills_path",
+                    name
+                )));
+            }
+        }
+
+        if let Some(host) = &self.default_host {
+            if host.trim().is_empty() || host.contains(char::is_whitespace) {
+                return Err(CapsyncError::ConfigInvalid(format!(
+                    "default_host must be a host name like gitlab.example.com, not '{}'",
+                    host
+                )));
+            }
+        }
         Ok(())
     }
 }
 
-pub fn load_config() -> Result<Config> {
-    let config_path = get_config_path();
-    let content = std::fs::read_to_string(config_path)?;
-    let config: Config = toml::from_str(&content)?;
+pub fn load_config() -> Result<Config, CapsyncError> {
+    load_config_from(&get_config_path())
+}
+
+pub fn load_config_from(config_path: &Path) -> Result<Config, CapsyncError> {
+    let content = std::fs::read_to_string(config_path).map_err(|e| {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            CapsyncError::ConfigNotFound {
+                path: config_path.to_path_buf(),
+            }
+        } else {
+            e.into()
+        }
+    })?;
+    let config: Config = toml::from_str(&content).map_err(|e| CapsyncError::ConfigParse {
+        path: config_path.to_path_buf(),
+        message: e.to_string(),
+    })?;
     config.validate()?;
     Ok(config)
 }
 
 pub fn save_config(config: &Config) -> Result<()> {
-    let config_path = get_config_path();
+    save_config_to(config, &get_config_path())
+}
+
+pub fn save_config_to(config: &Config, config_path: &Path) -> Result<()> {
     if let Some(parent) = config_path.parent() {
         std::fs::create_dir_all(parent)?;
     }
@@ -82,7 +200,10 @@ pub fn save_config(config: &Config) -> Result<()> {
 }
 
 pub fn get_config_path() -> PathBuf {
-    dirs::home_dir()
-        .unwrap_or_else(|| PathBuf::from("~"))
-        .join(".config/capsync/config.toml")
+    paths::config_path()
+}
+
+/// Optional user registry that adds or overrides built-in tools.
+pub fn get_registry_path() -> PathBuf {
+    paths::registry_path()
 }
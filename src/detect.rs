@@ -1,17 +1,57 @@
-use crate::tools::all_tools;
+use crate::tools::ToolRegistry;
+use serde::Serialize;
+use std::path::PathBuf;
 
 pub struct ToolDetector;
 
+/// An installed tool and why CapSync thinks it is installed.
+#[derive(Debug, Clone, Serialize)]
+pub struct DetectedTool {
+    pub name: String,
+    /// The tool's detect path, which exists.
+    pub evidence: PathBuf,
+    pub skills_path: PathBuf,
+    pub commands_path: Option<PathBuf>,
+    /// Other detected tools that read the same skills directory.
+    pub shares_skills_path_with: Vec<String>,
+}
+
 impl ToolDetector {
+    /// Installed tools in [`ToolRegistry::configured`].
     pub fn detect_all() -> Vec<String> {
-        let mut detected = Vec::new();
+        Self::detect_in(&ToolRegistry::configured())
+    }
+
+    /// Installed tools in `registry`.
+    pub fn detect_in(registry: &ToolRegistry) -> Vec<String> {
+        registry
+            .all()
+            .iter()
+            .filter(|tool| tool.config_path.exists())
+            .map(|tool| tool.name.clone())
+            .collect()
+    }
 
-        for tool in all_tools() {
-            if tool.config_path.exists() {
-                detected.push(tool.name.to_string());
-            }
-        }
+    /// Detected tools with the path that gave each one away.
+    pub fn detect_with_evidence(registry: &ToolRegistry) -> Vec<DetectedTool> {
+        let detected = Self::detect_in(registry);
 
-        detected
+        registry
+            .all()
+            .iter()
+            .filter(|tool| detected.contains(&tool.name))
+            .map(|tool| DetectedTool {
+                name: tool.name.clone(),
+                evidence: tool.config_path.clone(),
+                skills_path: tool.skills_path.clone(),
+                commands_path: tool.commands_path.clone(),
+                shares_skills_path_with: registry
+                    .tools_sharing_skills_path(&tool.name)
+                    .into_iter()
+                    .filter(|other| detected.iter().any(|name| name == other))
+                    .map(str::to_string)
+                    .collect(),
+            })
+            .collect()
     }
 }
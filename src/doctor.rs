@@ -0,0 +1,349 @@
+use crate::clone::has_unpushed_changes;
+use crate::config::{self, Config};
+use crate::detect::ToolDetector;
+use crate::install::read_skill_name;
+use crate::status::{self, LinkState};
+use crate::sync::{SyncManager, SyncResult};
+use crate::validate::Severity;
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A change `doctor --fix` may make on its own because it never loses data.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "action", rename_all = "kebab-case")]
+pub enum SafeFix {
+    CreateDirectory { path: PathBuf },
+    Sync,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Finding {
+    pub severity: Severity,
+    /// Which check produced the finding, such as `config` or `destinations`.
+    pub check: &'static str,
+    pub message: String,
+    /// A command or edit that resolves the finding.
+    pub suggestion: Option<String>,
+    pub safe_fix: Option<SafeFix>,
+}
+
+impl Finding {
+    fn new(severity: Severity, check: &'static str, message: String) -> Self {
+        Self {
+            severity,
+            check,
+            message,
+            suggestion: None,
+            safe_fix: None,
+        }
+    }
+
+    fn suggest(mut self, suggestion: impl Into<String>) -> Self {
+        self.suggestion = Some(suggestion.into());
+        self
+    }
+
+    fn fix(mut self, fix: SafeFix) -> Self {
+        self.safe_fix = Some(fix);
+        self
+    }
+}
+
+/// Runs every check against the config file at `config_path`.
+pub fn diagnose(config_path: &Path) -> Vec<Finding> {
+    let config = match config::load_config_from(config_path) {
+        Ok(config) => config,
+        Err(e) => {
+            return vec![
+                Finding::new(
+                    Severity::Error,
+                    "config",
+                    format!("Cannot load {}: {:#}", config_path.display(), e),
+                )
+                .suggest("capsync init"),
+            ];
+        }
+    };
+
+    let mut findings = Vec::new();
+    check_sources(&config, &mut findings);
+    check_detection(&config, &mut findings);
+    check_destinations(&config, config_path, &mut findings);
+    check_shared_paths(&config, &mut findings);
+    check_git(&config, &mut findings);
+    check_skills(&config, &mut findings);
+    findings
+}
+
+/// Applies the safe fixes among `findings` and returns the sync result, if a
+/// sync was needed.
+pub fn apply_safe_fixes(findings: &[Finding], config: &Config) -> Result<Option<SyncResult>> {
+    let mut needs_sync = false;
+
+    for fix in findings
+        .iter()
+        .filter_map(|finding| finding.safe_fix.as_ref())
+    {
+        match fix {
+            SafeFix::CreateDirectory { path } => fs::create_dir_all(path)
+                .with_context(|| format!("Failed to create directory {}", path.display()))?,
+            SafeFix::Sync => needs_sync = true,
+        }
+    }
+
+    if needs_sync {
+        return Ok(Some(SyncManager::sync_all(config)?));
+    }
+
+    Ok(None)
+}
+
+fn check_sources(config: &Config, findings: &mut Vec<Finding>) {
+    check_source("skills_source", &config.skills_source, findings);
+
+    if let Some(commands_source) = config
+        .commands_source
+        .as_deref()
+        .filter(|source| !source.as_os_str().is_empty())
+    {
+        check_source("commands_source", commands_source, findings);
+    }
+}
+
+fn check_source(key: &str, path: &Path, findings: &mut Vec<Finding>) {
+    if !path.exists() {
+        findings.push(
+            Finding::new(
+                Severity::Error,
+                "sources",
+                format!("{} {} does not exist", key, path.display()),
+            )
+            .suggest(format!("mkdir -p {}", path.display()))
+            .fix(SafeFix::CreateDirectory {
+                path: path.to_path_buf(),
+            }),
+        );
+    } else if !path.is_dir() {
+        findings.push(
+            Finding::new(
+                Severity::Error,
+                "sources",
+                format!("{} {} is not a directory", key, path.display()),
+            )
+            .suggest(format!("point {} at a directory in the config", key)),
+        );
+    } else if let Err(e) = fs::read_dir(path) {
+        findings.push(
+            Finding::new(
+                Severity::Error,
+                "sources",
+                format!("{} {} cannot be read: {}", key, path.display(), e),
+            )
+            .suggest(format!("chmod u+rx {}", path.display())),
+        );
+    }
+}
+
+fn check_detection(config: &Config, findings: &mut Vec<Finding>) {
+    let registry = match config.tool_registry() {
+        Ok(registry) => registry,
+        Err(e) => {
+            findings.push(
+                Finding::new(Severity::Error, "tools", format!("{:#}", e)).suggest(format!(
+                    "capsync tools validate {}",
+                    config::get_registry_path().display()
+                )),
+            );
+            return;
+        }
+    };
+    let detected = ToolDetector::detect_in(&registry);
+
+    for (name, dest) in sorted_enabled(config) {
+        if registry.get(name).is_none() {
+            findings.push(Finding::new(
+                Severity::Info,
+                "tools",
+                format!(
+                    "{} is not a known tool; its paths are taken from the config as-is",
+                    name
+                ),
+            ));
+        } else if !detected.contains(name) {
+            findings.push(
+                Finding::new(
+                    Severity::Warning,
+                    "tools",
+                    format!(
+                        "{} is enabled but no longer detected (links at {})",
+                        name,
+                        dest.skills_path.display()
+                    ),
+                )
+                .suggest(format!("capsync remove {}", name)),
+            );
+        }
+    }
+}
+
+fn check_destinations(config: &Config, config_path: &Path, findings: &mut Vec<Finding>) {
+    let report = status::status_report(config, config_path);
+
+    for dest in report.out_of_sync() {
+        let paths = std::iter::once(&dest.skills).chain(dest.commands.as_ref());
+        for path_status in paths.filter(|status| !status.state.is_in_sync()) {
+            let path = path_status.path.display();
+            let finding = match &path_status.state {
+                LinkState::ForeignDirectory | LinkState::ForeignFile => Finding::new(
+                    Severity::Error,
+                    "destinations",
+                    format!(
+                        "{}: {} exists and is not managed by CapSync",
+                        dest.name, path
+                    ),
+                )
+                .suggest(format!("capsync adopt {}", dest.name)),
+                LinkState::PerSkill(per_skill) if !per_skill.conflicts.is_empty() => Finding::new(
+                    Severity::Warning,
+                    "destinations",
+                    format!(
+                        "{}: {} has entries not managed by CapSync: {}",
+                        dest.name,
+                        path,
+                        per_skill.conflicts.join(", ")
+                    ),
+                )
+                .suggest(format!("move them into {}", config.skills_source.display())),
+                LinkState::Unreadable { error } | LinkState::Error { error } => Finding::new(
+                    Severity::Error,
+                    "destinations",
+                    format!("{}: cannot inspect {}: {}", dest.name, path, error),
+                ),
+                state => Finding::new(
+                    Severity::Warning,
+                    "destinations",
+                    format!("{}: {} is {}", dest.name, path, describe(state)),
+                )
+                .suggest("capsync sync")
+                .fix(SafeFix::Sync),
+            };
+            findings.push(finding);
+        }
+    }
+}
+
+fn describe(state: &LinkState) -> String {
+    match state {
+        LinkState::LinkedElsewhere { target } => {
+            format!(
+                "linked to {} instead of the configured source",
+                target.display()
+            )
+        }
+        LinkState::Broken { target } => format!("a broken link to {}", target.display()),
+        LinkState::Missing => "not synced".to_string(),
+        LinkState::WholeDirectoryLink { .. } => {
+            "a whole-directory link but its link_mode expects per-entry links".to_string()
+        }
+        LinkState::PerSkill(_) => "missing per-skill links or has stale ones".to_string(),
+        LinkState::Mirror(_) => "out of date with the source".to_string(),
+        _ => "out of sync".to_string(),
+    }
+}
+
+fn check_shared_paths(config: &Config, findings: &mut Vec<Finding>) {
+    for (name, dest) in sorted_enabled(config) {
+        for other in SyncManager::tools_sharing_paths(name, config) {
+            if other.as_str() < name.as_str() {
+                continue;
+            }
+
+            let other_mode = config.destinations[&other].link_mode;
+            if other_mode != dest.link_mode {
+                findings.push(
+                    Finding::new(
+                        Severity::Error,
+                        "shared-paths",
+                        format!(
+                            "{} and {} share a directory but use different link modes",
+                            name, other
+                        ),
+                    )
+                    .suggest(format!(
+                        "give {} and {} the same link_mode in {}",
+                        name,
+                        other,
+                        config::get_config_path().display()
+                    )),
+                );
+            }
+        }
+    }
+}
+
+fn check_git(config: &Config, findings: &mut Vec<Finding>) {
+    let source = &config.skills_source;
+    if source.join(".git").exists() && has_unpushed_changes(source) {
+        findings.push(
+            Finding::new(
+                Severity::Warning,
+                "git",
+                format!("{} has uncommitted or unpushed changes", source.display()),
+            )
+            .suggest(format!("git -C {} status", source.display())),
+        );
+    }
+}
+
+fn check_skills(config: &Config, findings: &mut Vec<Finding>) {
+    let Ok(entries) = fs::read_dir(&config.skills_source) else {
+        return;
+    };
+
+    let mut skills: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| !entry.file_name().to_string_lossy().starts_with('.'))
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect();
+    skills.sort();
+
+    for skill in skills {
+        let skill_markdown = skill.join("SKILL.md");
+        let problem = if !skill_markdown.is_file() {
+            Some("has no SKILL.md".to_string())
+        } else {
+            match read_skill_name(&skill_markdown) {
+                Ok(Some(name)) if !name.is_empty() => None,
+                Ok(_) => Some("has a SKILL.md without a name in its frontmatter".to_string()),
+                Err(e) => Some(format!("has an unreadable SKILL.md: {:#}", e)),
+            }
+        };
+
+        if let Some(problem) = problem {
+            findings.push(
+                Finding::new(
+                    Severity::Warning,
+                    "skills",
+                    format!("{} {}", skill.display(), problem),
+                )
+                .suggest(format!(
+                    "give {} a name and description in its SKILL.md frontmatter",
+                    skill.display()
+                )),
+            );
+        }
+    }
+}
+
+fn sorted_enabled(config: &Config) -> Vec<(&String, &config::DestinationConfig)> {
+    let mut enabled: Vec<_> = config
+        .destinations
+        .iter()
+        .filter(|(_, dest)| dest.enabled)
+        .collect();
+    enabled.sort_by(|a, b| a.0.cmp(b.0));
+    enabled
+}
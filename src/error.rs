@@ -0,0 +1,146 @@
+use crate::sync::SourceKind;
+use std::path::PathBuf;
+use thiserror::Error;
+
+/// Failures callers of the library may want to tell apart. Anything else is
+/// carried in [`CapsyncError::Other`].
+#[derive(Debug, Error)]
+pub enum CapsyncError {
+    #[error("No configuration found at {}. Run 'capsync init' to create one.", .path.display())]
+    ConfigNotFound { path: PathBuf },
+
+    #[error("Failed to parse configuration at {}: {message}", .path.display())]
+    ConfigParse { path: PathBuf, message: String },
+
+    #[error("{0}")]
+    ConfigInvalid(String),
+
+    #[error("Unknown destination: {0}")]
+    UnknownDestination(String),
+
+    #[error("{} source directory does not exist: {}", source_label(*.kind), .path.display())]
+    SourceMissing { kind: SourceKind, path: PathBuf },
+
+    #[error(
+        "Destination at {} already exists and is not a symlink. Not replacing it; run 'capsync adopt {tool}' to move its contents into the source, or 'capsync adopt {tool} --backup' to back it up first",
+        .path.display()
+    )]
+    DestinationConflict { tool: String, path: PathBuf },
+
+    #[error("Failed to sync: {}", .failures.iter().map(|(name, _)| name.as_str()).collect::<Vec<_>>().join(", "))]
+    SyncFailed { failures: Vec<(String, String)> },
+
+    #[error("Failed to install: {}", .failures.iter().map(|(name, _)| name.as_str()).collect::<Vec<_>>().join(", "))]
+    InstallFailed { failures: Vec<(String, String)> },
+
+    #[error("Failed to update: {}", .failures.iter().map(|(name, _)| name.as_str()).collect::<Vec<_>>().join(", "))]
+    UpdateFailed { failures: Vec<(String, String)> },
+
+    #[error("{count} enabled destination(s) out of sync")]
+    OutOfSync { count: usize },
+
+    #[error("doctor found {count} error(s)")]
+    ChecksFailed { count: usize },
+
+    #[error("{0}")]
+    InvalidReference(String),
+
+    #[error("{0}")]
+    SkillNotFound(String),
+
+    #[error("Skill at {} is not valid:\n  {}", .path.display(), .problems.join("\n  "))]
+    InvalidSkill {
+        path: PathBuf,
+        problems: Vec<String>,
+    },
+
+    #[error("validation found {count} error(s)")]
+    ValidationFailed { count: usize },
+
+    #[error("Cannot update {}: working tree has uncommitted or unpushed changes. Commit or stash them first.", .0.display())]
+    UncommittedChanges(PathBuf),
+
+    #[error("Git operation failed: {0}")]
+    Git(#[from] git2::Error),
+
+    #[error("Aborted.")]
+    Aborted,
+
+    #[error(
+        "Cannot ask \"{question}\" without a terminal{}",
+        .flag.as_ref().map(|flag| format!("; pass {flag} to answer")).unwrap_or_default()
+    )]
+    InputRequired {
+        question: String,
+        flag: Option<String>,
+    },
+
+    #[error(transparent)]
+    Other(anyhow::Error),
+}
+
+impl CapsyncError {
+    /// The process exit code `capsync` uses for this error. These values are
+    /// stable; 2 is reserved for command-line usage errors.
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            CapsyncError::Other(_) => 1,
+            CapsyncError::ConfigNotFound { .. }
+            | CapsyncError::ConfigParse { .. }
+            | CapsyncError::ConfigInvalid(_)
+            | CapsyncError::UnknownDestination(_) => 3,
+            CapsyncError::SourceMissing { .. }
+            | CapsyncError::DestinationConflict { .. }
+            | CapsyncError::SyncFailed { .. } => 4,
+            CapsyncError::InvalidReference(_)
+            | CapsyncError::SkillNotFound(_)
+            | CapsyncError::InvalidSkill { .. }
+            | CapsyncError::InstallFailed { .. }
+            | CapsyncError::UpdateFailed { .. } => 5,
+            CapsyncError::Git(_) | CapsyncError::UncommittedChanges(_) => 6,
+            CapsyncError::Aborted => 7,
+            CapsyncError::InputRequired { .. } => 8,
+            CapsyncError::OutOfSync { .. }
+            | CapsyncError::ChecksFailed { .. }
+            | CapsyncError::ValidationFailed { .. } => 9,
+        }
+    }
+
+    /// Finds the `CapsyncError` behind an `anyhow` error, if there is one.
+    pub fn find(error: &anyhow::Error) -> Option<&CapsyncError> {
+        error.chain().find_map(|cause| cause.downcast_ref())
+    }
+
+    /// Names the flag that answers a question asked without a terminal.
+    pub fn with_flag(self, flag: &str) -> Self {
+        match self {
+            CapsyncError::InputRequired { question, .. } => CapsyncError::InputRequired {
+                question,
+                flag: Some(flag.to_string()),
+            },
+            other => other,
+        }
+    }
+}
+
+impl From<anyhow::Error> for CapsyncError {
+    fn from(error: anyhow::Error) -> Self {
+        match error.downcast::<CapsyncError>() {
+            Ok(error) => error,
+            Err(error) => CapsyncError::Other(error),
+        }
+    }
+}
+
+impl From<std::io::Error> for CapsyncError {
+    fn from(error: std::io::Error) -> Self {
+        CapsyncError::Other(error.into())
+    }
+}
+
+fn source_label(kind: SourceKind) -> &'static str {
+    match kind {
+        SourceKind::Skills => "Skills",
+        SourceKind::Commands => "Commands",
+    }
+}
@@ -70,6 +70,62 @@ pub fn clone_to_path(url: &str, branch: &str, target: &Path) -> Result<()> {
     Ok(())
 }
 
+/// The commit checked out in the repository at `path`.
+pub fn head_commit(path: &Path) -> Result<String> {
+    let repository = Repository::open(path)
+        .with_context(|| format!("Failed to open repository at {}", path.display()))?;
+    let commit = repository
+        .head()
+        .and_then(|head| head.peel_to_commit())
+        .context("Failed to read HEAD commit")?;
+    Ok(commit.id().to_string())
+}
+
+/// The branch checked out in the repository at `path`, or `None` when HEAD is detached.
+pub fn head_branch(path: &Path) -> Result<Option<String>> {
+    let repository = Repository::open(path)
+        .with_context(|| format!("Failed to open repository at {}", path.display()))?;
+    let head = repository.head().context("Failed to read HEAD")?;
+    if !head.is_branch() {
+        return Ok(None);
+    }
+    Ok(head.shorthand().ok().map(str::to_string))
+}
+
+/// Checks out `revision` (a tag, a commit, or a branch of `origin`) in the
+/// repository at `path`, detaching HEAD. Returns the commit, or `None` when
+/// the repository has no such revision.
+pub fn checkout_revision(path: &Path, revision: &str) -> Result<Option<String>> {
+    let repository = Repository::open(path)
+        .with_context(|| format!("Failed to open repository at {}", path.display()))?;
+
+    let candidates = [
+        format!("refs/tags/{}", revision),
+        format!("refs/remotes/origin/{}", revision),
+        revision.to_string(),
+    ];
+    let Some(object) = candidates
+        .iter()
+        .find_map(|spec| repository.revparse_single(spec).ok())
+    else {
+        return Ok(None);
+    };
+    let Ok(commit) = object.peel_to_commit() else {
+        return Ok(None);
+    };
+
+    let mut checkout = git2::build::CheckoutBuilder::new();
+    checkout.force();
+    repository
+        .checkout_tree(commit.as_object(), Some(&mut checkout))
+        .with_context(|| format!("Failed to check out {}", revision))?;
+    repository
+        .set_head_detached(commit.id())
+        .with_context(|| format!("Failed to check out {}", revision))?;
+
+    Ok(Some(commit.id().to_string()))
+}
+
 pub(crate) fn build_fetch_options() -> git2::FetchOptions<'static> {
     let mut fetch_options = git2::FetchOptions::new();
     fetch_options.download_tags(git2::AutotagOption::All);
@@ -1,15 +1,56 @@
+use crate::archive;
 use crate::config::Config;
-use crate::git::clone_to_path;
+use crate::error::CapsyncError;
+use crate::git::{checkout_revision, clone_to_path, head_branch, head_commit};
+use crate::lockfile::{LockedSkill, Lockfile, hash_skill_tree};
+use crate::paths;
+use crate::ui::{Prompter, Reporter};
+use crate::validate::Severity;
+use crate::validate::{Frontmatter, validate_skill};
 use anyhow::{Context, Result, anyhow};
 use git2::Repository;
+use std::collections::HashMap;
 use std::fs;
-use std::io::{self, Write};
 use std::path::{Component, Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
+use tempfile::TempDir;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct InstallOptions {
     pub reference: String,
+    pub existing: ExistingSkill,
+    /// A tag or commit to install from, like `@<ref>` on the reference.
+    pub revision: Option<String>,
+}
+
+/// What to do when a skill with the same name is already installed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExistingSkill {
+    /// Ask before replacing it.
+    #[default]
+    Prompt,
+    Replace,
+    /// Keep the installed copy and report it as skipped.
+    Skip,
+}
+
+/// Which skills of a repository to install.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SkillSet {
+    All,
+    /// Skills matched by directory name or frontmatter `name`, like the last
+    /// segment of `owner/repo/skill-slug`.
+    Named(Vec<String>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepoInstallOptions {
+    /// A repository reference such as `owner/repo`.
+    pub reference: String,
+    pub skills: SkillSet,
+    pub existing: ExistingSkill,
+    /// A tag or commit to install from, like `@<ref>` on the reference.
+    pub revision: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -17,6 +58,9 @@ pub struct InstallResult {
     pub skill_slug: String,
     pub installed_path: PathBuf,
     pub replaced_existing: bool,
+    pub skipped_existing: bool,
+    /// The skill directory relative to the checkout root.
+    pub source_subpath: PathBuf,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -25,19 +69,111 @@ pub enum SkillSelector {
     Path(PathBuf),
 }
 
+/// Outcome of one skill in a repository install.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SkillInstallStatus {
+    Installed(InstallResult),
+    Failed(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkillInstall {
+    /// The skill's path in the repository, or the name asked for with `--skill`.
+    pub name: String,
+    pub status: SkillInstallStatus,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RepoInstallResult {
+    pub skills: Vec<SkillInstall>,
+}
+
+impl RepoInstallResult {
+    pub fn installed(&self) -> impl Iterator<Item = &InstallResult> {
+        self.skills.iter().filter_map(|skill| match &skill.status {
+            SkillInstallStatus::Installed(result) => Some(result),
+            SkillInstallStatus::Failed(_) => None,
+        })
+    }
+
+    pub fn failures(&self) -> Vec<(String, String)> {
+        self.skills
+            .iter()
+            .filter_map(|skill| match &skill.status {
+                SkillInstallStatus::Failed(error) => Some((skill.name.clone(), error.clone())),
+                SkillInstallStatus::Installed(_) => None,
+            })
+            .collect()
+    }
+
+    pub fn report(&self, reporter: &dyn Reporter) {
+        for skill in &self.skills {
+            match &skill.status {
+                SkillInstallStatus::Installed(result) if result.skipped_existing => {
+                    reporter.info(&format!(
+                        "  {}: already installed at {}, skipped",
+                        result.skill_slug,
+                        result.installed_path.display()
+                    ))
+                }
+                SkillInstallStatus::Installed(result) if result.replaced_existing => {
+                    reporter.info(&format!(
+                        "  {}: replaced {}",
+                        result.skill_slug,
+                        result.installed_path.display()
+                    ))
+                }
+                SkillInstallStatus::Installed(result) => reporter.info(&format!(
+                    "  {}: installed to {}",
+                    result.skill_slug,
+                    result.installed_path.display()
+                )),
+                SkillInstallStatus::Failed(error) => {
+                    reporter.warn(&format!("  {}: {}", skill.name, error));
+                }
+            }
+        }
+    }
+}
+
+/// A repository to install several skills from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedRepoRef {
+    pub repo_url: String,
+    pub branch: Option<String>,
+    /// A tag or commit to check out instead of the tip of the branch.
+    pub revision: Option<String>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ResolvedInstallRef {
     pub repo_url: String,
     pub branch: Option<String>,
+    /// A tag or commit to check out instead of the tip of the branch.
+    pub revision: Option<String>,
     pub selector: SkillSelector,
 }
 
+/// Host of the forge that `owner/repo/...` shorthand expands to when the
+/// config does not set `default_host`.
+pub const DEFAULT_HOST: &str = "github.com";
+
+/// Resolves a reference against [`DEFAULT_HOST`].
 pub fn resolve_install_ref(input: &str) -> Result<ResolvedInstallRef> {
+    resolve_install_ref_with_host(input, DEFAULT_HOST)
+}
+
+/// Resolves a skill reference. `owner/repo/...` shorthand expands to
+/// `default_host`, which is a host name like `gitlab.example.com` or a base URL.
+pub fn resolve_install_ref_with_host(
+    input: &str,
+    default_host: &str,
+) -> Result<ResolvedInstallRef> {
     let trimmed_input = input.trim();
 
     if trimmed_input.is_empty() {
         return Err(anyhow!(
-            "Install reference cannot be empty. Use an HTTPS skills.sh URL, GitHub tree URL, or owner/repo/skill reference."
+            "Install reference cannot be empty. Use an HTTPS skills.sh URL, tree URL, or owner/repo/skill reference."
         ));
     }
 
@@ -48,6 +184,7 @@ pub fn resolve_install_ref(input: &str) -> Result<ResolvedInstallRef> {
     }
 
     if let Some(path) = trimmed_input.strip_prefix("https://skills.sh/") {
+        let (path, revision) = split_revision(path)?;
         let parts: Vec<&str> = path.split('/').filter(|part| !part.is_empty()).collect();
         if parts.len() == 3 {
             let owner = parts[0];
@@ -56,6 +193,7 @@ pub fn resolve_install_ref(input: &str) -> Result<ResolvedInstallRef> {
             return Ok(ResolvedInstallRef {
                 repo_url: format!("https://github.com/{owner}/{repository_name}.git"),
                 branch: None,
+                revision,
                 selector: SkillSelector::Slug(skill_slug.to_string()),
             });
         }
@@ -65,12 +203,26 @@ pub fn resolve_install_ref(input: &str) -> Result<ResolvedInstallRef> {
         ));
     }
 
-    if let Some(tree_ref) = resolve_github_tree_ref(trimmed_input)? {
-        return Ok(tree_ref);
+    if let Some(tree_url) = parse_tree_url(trimmed_input)? {
+        let Some(skill_path) = tree_url.path.filter(|path| !path.as_os_str().is_empty()) else {
+            return Err(anyhow!(
+                "{} tree URLs must point to a concrete skill directory",
+                tree_url.forge
+            ));
+        };
+
+        return Ok(ResolvedInstallRef {
+            repo_url: tree_url.repo_url,
+            branch: tree_url.branch,
+            revision: tree_url.revision,
+            selector: SkillSelector::Path(skill_path),
+        });
     }
 
     if !trimmed_input.contains("://") && !trimmed_input.starts_with("git@") {
-        let parts: Vec<&str> = trimmed_input
+        let base_url = host_base_url(default_host);
+        let (path, revision) = split_revision(trimmed_input)?;
+        let parts: Vec<&str> = path
             .trim_end_matches('/')
             .split('/')
             .filter(|part| !part.is_empty())
@@ -78,140 +230,781 @@ pub fn resolve_install_ref(input: &str) -> Result<ResolvedInstallRef> {
 
         return match parts.as_slice() {
             [owner, repository_name, skill_slug] => Ok(ResolvedInstallRef {
-                repo_url: format!("https://github.com/{owner}/{repository_name}.git"),
+                repo_url: format!("{base_url}/{owner}/{repository_name}.git"),
                 branch: None,
+                revision,
                 selector: SkillSelector::Slug((*skill_slug).to_string()),
             }),
             [owner, repository_name, remaining_path @ ..] if remaining_path.len() > 1 => {
                 Ok(ResolvedInstallRef {
-                    repo_url: format!("https://github.com/{owner}/{repository_name}.git"),
+                    repo_url: format!("{base_url}/{owner}/{repository_name}.git"),
                     branch: None,
+                    revision,
                     selector: SkillSelector::Path(PathBuf::from(remaining_path.join("/"))),
                 })
             }
             [_, _] => Err(anyhow!(
-                "Install requires a concrete skill reference, like owner/repo/skill-slug or owner/repo/path/to/skill"
+                "Install requires a concrete skill reference, like owner/repo/skill-slug or owner/repo/path/to/skill. Pass --all or --skill <name> to install from the whole repository."
             )),
             _ => Err(anyhow!(
-                "Unsupported install reference. Use an HTTPS skills.sh URL, GitHub tree URL, or owner/repo/skill reference."
+                "Unsupported install reference. Use an HTTPS skills.sh URL, tree URL, or owner/repo/skill reference."
             )),
         };
     }
 
     Err(anyhow!(
-        "Unsupported install reference. Use an HTTPS skills.sh URL, GitHub tree URL, or owner/repo/skill reference."
+        "Unsupported install reference. Use an HTTPS skills.sh URL, tree URL, or owner/repo/skill reference."
     ))
 }
 
-fn resolve_github_tree_ref(input: &str) -> Result<Option<ResolvedInstallRef>> {
-    let normalized_input = input.trim_end_matches('/');
-    let prefix = "https://github.com/";
+/// Resolves a repository reference against [`DEFAULT_HOST`].
+pub fn resolve_repo_ref(input: &str) -> Result<ResolvedRepoRef> {
+    resolve_repo_ref_with_host(input, DEFAULT_HOST)
+}
 
-    if !normalized_input.starts_with(prefix) {
-        return Ok(None);
+/// Resolves a reference to a whole repository: `owner/repo` on
+/// `default_host`, an HTTPS repository URL (GitLab subgroups included), a tree
+/// URL naming only a branch, or `https://skills.sh/owner/repo`.
+pub fn resolve_repo_ref_with_host(input: &str, default_host: &str) -> Result<ResolvedRepoRef> {
+    let trimmed_input = input.trim().trim_end_matches('/');
+    let concrete_error = || {
+        anyhow!(
+            "--all and --skill take a repository reference like owner/repo, not '{}'",
+            input.trim()
+        )
+    };
+
+    if let Some(tree_url) = parse_tree_url(trimmed_input)? {
+        if tree_url
+            .path
+            .is_some_and(|path| !path.as_os_str().is_empty())
+        {
+            return Err(concrete_error());
+        }
+        return Ok(ResolvedRepoRef {
+            repo_url: tree_url.repo_url,
+            branch: tree_url.branch,
+            revision: tree_url.revision,
+        });
     }
 
-    let path = &normalized_input[prefix.len()..];
-    let parts: Vec<&str> = path.split('/').filter(|part| !part.is_empty()).collect();
+    // Only GitLab nests repositories below subgroups, so a longer path is
+    // taken as the repository on self-hosted forges and rejected elsewhere.
+    let (base_url, path, nested) =
+        if let Some(path) = trimmed_input.strip_prefix("https://skills.sh/") {
+            ("https://github.com".to_string(), path, false)
+        } else if let Some(rest) = trimmed_input.strip_prefix("https://") {
+            let (host, path) = rest.split_once('/').unwrap_or((rest, ""));
+            (format!("https://{host}"), path, host != "github.com")
+        } else if !trimmed_input.contains("://") && !trimmed_input.starts_with("git@") {
+            (host_base_url(default_host), trimmed_input, false)
+        } else {
+            return Err(anyhow!(
+                "Unsupported repository reference. Use owner/repo or an HTTPS repository URL."
+            ));
+        };
 
-    if parts.len() < 3 {
-        return Ok(None);
+    let (path, revision) = split_revision(path)?;
+    let parts: Vec<&str> = path.split('/').filter(|part| !part.is_empty()).collect();
+    if parts.len() < 2 || (parts.len() > 2 && !nested) {
+        return Err(concrete_error());
     }
 
-    if parts[2] != "tree" {
-        return Ok(None);
-    }
+    Ok(ResolvedRepoRef {
+        repo_url: format!(
+            "{}/{}.git",
+            base_url,
+            parts.join("/").trim_end_matches(".git")
+        ),
+        branch: None,
+        revision,
+    })
+}
 
-    if parts.len() == 4 {
+/// Splits a pinned `@<tag|sha>` off a reference path such as
+/// `owner/repo/skill@v1.3`. Owner and repository names cannot contain `@`, so
+/// everything after the first one is the revision, `/` included.
+fn split_revision(path: &str) -> Result<(&str, Option<String>)> {
+    let Some((path, revision)) = path.split_once('@') else {
+        return Ok((path, None));
+    };
+    if revision.trim().is_empty() {
         return Err(anyhow!(
-            "GitHub tree URLs must point to a concrete skill directory"
+            "Name a tag or commit after '@', like owner/repo/skill@v1.0"
         ));
     }
+    Ok((path, Some(revision.trim().to_string())))
+}
 
-    if parts.len() < 5 {
-        return Ok(None);
+/// Whether a ref in a tree URL is a full commit id rather than a branch.
+fn is_commit_id(reference: &str) -> bool {
+    reference.len() == 40 && reference.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// `https://` base URL for a configured host, which may be given with or
+/// without a scheme.
+fn host_base_url(host: &str) -> String {
+    let host = host.trim().trim_end_matches('/');
+    if host.contains("://") {
+        host.to_string()
+    } else {
+        format!("https://{host}")
     }
+}
+
+/// A browse URL split into the repository, the ref, and the path inside it.
+struct TreeUrl {
+    forge: &'static str,
+    repo_url: String,
+    branch: Option<String>,
+    revision: Option<String>,
+    path: Option<PathBuf>,
+}
 
-    let owner = parts[0];
-    let repository_name = parts[1];
-    let branch_name = decode_url_path_component(parts[3])?;
-    let skill_path = parts[4..]
+/// Recognizes the directory URLs forges show in the browser, on any host:
+///
+/// - GitLab: `<group>/<subgroup>/<repo>/-/tree/<ref>/<path>`
+/// - GitHub: `<owner>/<repo>/tree/<ref>/<path>`
+/// - Gitea and Forgejo: `<owner>/<repo>/src/{branch,tag,commit}/<ref>/<path>`
+/// - Bitbucket: `<workspace>/<repo>/src/<ref>/<path>`
+///
+/// A ref containing `/` must be URL-encoded (`feature%2Fname`). Gitea tags and
+/// commits, and full commit ids elsewhere, become the revision to check out.
+fn parse_tree_url(input: &str) -> Result<Option<TreeUrl>> {
+    let Some(rest) = input.trim_end_matches('/').strip_prefix("https://") else {
+        return Ok(None);
+    };
+    let Some((host, path)) = rest.split_once('/') else {
+        return Ok(None);
+    };
+    let parts: Vec<&str> = path.split('/').filter(|part| !part.is_empty()).collect();
+
+    let (forge, repository, tail, pinned) =
+        if let Some(dash) = parts.iter().position(|part| *part == "-") {
+            if dash < 2 || parts.get(dash + 1) != Some(&"tree") {
+                return Ok(None);
+            }
+            ("GitLab", &parts[..dash], &parts[dash + 2..], false)
+        } else if parts.len() >= 3 {
+            match (parts[2], parts.get(3).copied()) {
+                ("tree", _) => ("GitHub", &parts[..2], &parts[3..], false),
+                ("src", Some("branch")) => ("Gitea", &parts[..2], &parts[4..], false),
+                ("src", Some("tag" | "commit")) => ("Gitea", &parts[..2], &parts[4..], true),
+                ("src", _) => ("Bitbucket", &parts[..2], &parts[3..], false),
+                _ => return Ok(None),
+            }
+        } else {
+            return Ok(None);
+        };
+
+    let context = format!("{} tree URL", forge);
+    let Some((reference, skill_path)) = tail.split_first() else {
+        return Err(anyhow!("{} tree URLs must name a branch", forge));
+    };
+    let reference = decode_url_path_component(reference, &context)?;
+    let skill_path = skill_path
         .iter()
-        .map(|segment| decode_url_path_component(segment))
+        .map(|segment| decode_url_path_component(segment, &context))
         .collect::<Result<Vec<_>>>()?
         .join("/");
-    let skill_path = PathBuf::from(skill_path);
-
-    if skill_path.as_os_str().is_empty() {
-        return Err(anyhow!(
-            "GitHub tree URLs must point to a concrete skill directory"
-        ));
-    }
 
-    Ok(Some(ResolvedInstallRef {
-        repo_url: format!("https://github.com/{owner}/{repository_name}.git"),
-        branch: Some(branch_name),
-        selector: SkillSelector::Path(skill_path),
+    Ok(Some(TreeUrl {
+        forge,
+        repo_url: format!("https://{}/{}.git", host, repository.join("/")),
+        branch: (!pinned && !is_commit_id(&reference)).then(|| reference.clone()),
+        revision: (pinned || is_commit_id(&reference)).then_some(reference),
+        path: Some(PathBuf::from(skill_path)),
     }))
 }
 
-fn decode_url_path_component(component: &str) -> Result<String> {
+fn decode_url_path_component(component: &str, context: &str) -> Result<String> {
+    let invalid = || {
+        anyhow!(
+            "Invalid percent-encoding in {} component: {}",
+            context,
+            component
+        )
+    };
     let bytes = component.as_bytes();
-    let mut decoded = String::with_capacity(component.len());
+    let mut decoded = Vec::with_capacity(bytes.len());
     let mut index = 0;
 
     while index < bytes.len() {
         if bytes[index] == b'%' {
-            if index + 2 >= bytes.len() {
-                return Err(anyhow!(
-                    "Invalid percent-encoding in GitHub tree URL component: {}",
-                    component
-                ));
+            let hex = bytes.get(index + 1..index + 3).ok_or_else(invalid)?;
+            if !hex.iter().all(u8::is_ascii_hexdigit) {
+                return Err(invalid());
             }
-
-            let hex = &component[index + 1..index + 3];
-            let value = u8::from_str_radix(hex, 16).map_err(|_| {
-                anyhow!(
-                    "Invalid percent-encoding in GitHub tree URL component: {}",
-                    component
-                )
-            })?;
-            decoded.push(value as char);
+            let hex = std::str::from_utf8(hex).map_err(|_| invalid())?;
+            decoded.push(u8::from_str_radix(hex, 16).map_err(|_| invalid())?);
             index += 3;
             continue;
         }
 
-        decoded.push(bytes[index] as char);
+        decoded.push(bytes[index]);
         index += 1;
     }
 
-    Ok(decoded)
+    String::from_utf8(decoded).map_err(|_| {
+        anyhow!(
+            "{} component is not valid UTF-8 once decoded: {}",
+            context,
+            component
+        )
+    })
+}
+
+pub fn install_skill(
+    options: &InstallOptions,
+    config: &Config,
+    prompter: &dyn Prompter,
+    reporter: &dyn Reporter,
+) -> Result<InstallResult, CapsyncError> {
+    ensure_install_root_ready(&config.skills_source)?;
+
+    let local_source = resolve_local_source(&options.reference)
+        .map_err(|e| CapsyncError::InvalidReference(e.to_string()))?;
+    let mut lockfile = Lockfile::load(&config.skills_source)?;
+
+    let (tree, resolved_reference) = match local_source {
+        Some(local) => {
+            ensure_unpinned(&local, options.revision.as_deref())?;
+            ensure_outside_skills_source(&local, &config.skills_source)?;
+            let tree = SourceTree::open_local(&local)?;
+            let resolved_reference = ResolvedInstallRef {
+                repo_url: local.url(),
+                branch: None,
+                revision: None,
+                selector: SkillSelector::Path(single_skill_subpath(&tree.root)?),
+            };
+            (tree, resolved_reference)
+        }
+        None => {
+            let mut resolved_reference =
+                resolve_install_ref_with_host(&options.reference, config.install_host())
+                    .map_err(|e| CapsyncError::InvalidReference(e.to_string()))?;
+            resolved_reference.revision = pinned_revision(
+                resolved_reference.revision.take(),
+                options.revision.as_deref(),
+            )?;
+            reporter.info("Fetching skill source...");
+            let tree = SourceTree::clone_repository(
+                &resolved_reference.repo_url,
+                resolved_reference.branch.as_deref(),
+                resolved_reference.revision.as_deref(),
+            )?;
+            (tree, resolved_reference)
+        }
+    };
+
+    let result = install_skill_from_checkout(
+        &tree.root,
+        &resolved_reference,
+        &config.skills_source,
+        options.existing,
+        prompter,
+    )?;
+
+    if !result.skipped_existing {
+        let locked = tree.locked_skill(&options.reference, &resolved_reference, &result)?;
+        lockfile.insert(&result.skill_slug, locked);
+        lockfile.save(&config.skills_source)?;
+    }
+
+    Ok(result)
 }
 
-pub fn install_skill(options: &InstallOptions, config: &Config) -> Result<InstallResult> {
+/// Installs several skills from one repository, fetched once. A skill that
+/// fails is reported in the result and does not stop the others.
+pub fn install_repository(
+    options: &RepoInstallOptions,
+    config: &Config,
+    prompter: &dyn Prompter,
+    reporter: &dyn Reporter,
+) -> Result<RepoInstallResult, CapsyncError> {
     ensure_install_root_ready(&config.skills_source)?;
 
-    let resolved_reference = resolve_install_ref(&options.reference)?;
-    let temp_dir = tempfile::tempdir().context("Failed to create temporary directory")?;
+    let local_source = resolve_local_source(&options.reference)
+        .map_err(|e| CapsyncError::InvalidReference(e.to_string()))?;
+    let mut lockfile = Lockfile::load(&config.skills_source)?;
 
-    println!("Fetching skill source...");
-    clone_to_path(
-        &resolved_reference.repo_url,
-        resolved_reference.branch.as_deref().unwrap_or(""),
-        temp_dir.path(),
+    let (tree, repository) = match local_source {
+        Some(local) => {
+            ensure_unpinned(&local, options.revision.as_deref())?;
+            ensure_outside_skills_source(&local, &config.skills_source)?;
+            let repository = ResolvedRepoRef {
+                repo_url: local.url(),
+                branch: None,
+                revision: None,
+            };
+            (SourceTree::open_local(&local)?, repository)
+        }
+        None => {
+            let mut repository =
+                resolve_repo_ref_with_host(&options.reference, config.install_host())
+                    .map_err(|e| CapsyncError::InvalidReference(e.to_string()))?;
+            repository.revision =
+                pinned_revision(repository.revision.take(), options.revision.as_deref())?;
+            reporter.info("Fetching skill source...");
+            let tree = SourceTree::clone_repository(
+                &repository.repo_url,
+                repository.branch.as_deref(),
+                repository.revision.as_deref(),
+            )?;
+            (tree, repository)
+        }
+    };
+
+    let mut result = install_skills_from_checkout(
+        &tree.root,
+        &repository,
+        &options.skills,
+        &config.skills_source,
+        options.existing,
+        prompter,
     )?;
 
-    install_skill_from_checkout(temp_dir.path(), &resolved_reference, &config.skills_source)
+    for skill in &mut result.skills {
+        let SkillInstallStatus::Installed(installed) = &skill.status else {
+            continue;
+        };
+        if installed.skipped_existing {
+            continue;
+        }
+
+        let resolved_reference = ResolvedInstallRef {
+            repo_url: repository.repo_url.clone(),
+            branch: repository.branch.clone(),
+            revision: repository.revision.clone(),
+            selector: SkillSelector::Path(installed.source_subpath.clone()),
+        };
+        match tree.locked_skill(&options.reference, &resolved_reference, installed) {
+            Ok(locked) => lockfile.insert(&installed.skill_slug, locked),
+            Err(e) => skill.status = SkillInstallStatus::Failed(format!("{:#}", e)),
+        }
+    }
+
+    if result
+        .installed()
+        .any(|installed| !installed.skipped_existing)
+    {
+        lockfile.save(&config.skills_source)?;
+    }
+
+    Ok(result)
+}
+
+/// Installs the selected skills from an existing checkout. Skills whose slug
+/// was already taken earlier in the same run are reported as failures instead
+/// of replacing each other, and answering no at the replace prompt skips that
+/// skill.
+pub fn install_skills_from_checkout(
+    checkout_root: &Path,
+    repository: &ResolvedRepoRef,
+    skills: &SkillSet,
+    target_root: &Path,
+    existing: ExistingSkill,
+    prompter: &dyn Prompter,
+) -> Result<RepoInstallResult> {
+    let selectors: Vec<(String, SkillSelector)> = match skills {
+        SkillSet::All => {
+            let mut subpaths: Vec<PathBuf> = collect_skill_directories(checkout_root)?
+                .into_iter()
+                .filter_map(|dir| dir.strip_prefix(checkout_root).ok().map(Path::to_path_buf))
+                .filter(|subpath| {
+                    !subpath
+                        .components()
+                        .any(|part| part.as_os_str().to_string_lossy().starts_with('.'))
+                })
+                .collect();
+            subpaths.sort();
+            subpaths
+                .into_iter()
+                .map(|subpath| {
+                    let name = subpath.to_string_lossy().replace('\\', "/");
+                    (name, SkillSelector::Path(subpath))
+                })
+                .collect()
+        }
+        SkillSet::Named(names) => names
+            .iter()
+            .map(|name| (name.clone(), SkillSelector::Slug(name.clone())))
+            .collect(),
+    };
+
+    if selectors.is_empty() {
+        return Err(CapsyncError::SkillNotFound(format!(
+            "No skills with a SKILL.md found in {}",
+            repository.repo_url
+        ))
+        .into());
+    }
+
+    let mut result = RepoInstallResult::default();
+    let mut taken: HashMap<String, String> = HashMap::new();
+
+    for (name, selector) in selectors {
+        let resolved_reference = ResolvedInstallRef {
+            repo_url: repository.repo_url.clone(),
+            branch: repository.branch.clone(),
+            revision: repository.revision.clone(),
+            selector,
+        };
+
+        let status = match install_one_of_many(
+            checkout_root,
+            &resolved_reference,
+            target_root,
+            existing,
+            prompter,
+            &taken,
+        ) {
+            Ok(installed) => {
+                taken.insert(installed.skill_slug.clone(), name.clone());
+                SkillInstallStatus::Installed(installed)
+            }
+            Err(e) => SkillInstallStatus::Failed(format!("{:#}", e)),
+        };
+        result.skills.push(SkillInstall { name, status });
+    }
+
+    Ok(result)
+}
+
+fn install_one_of_many(
+    checkout_root: &Path,
+    resolved_reference: &ResolvedInstallRef,
+    target_root: &Path,
+    existing: ExistingSkill,
+    prompter: &dyn Prompter,
+    taken: &HashMap<String, String>,
+) -> Result<InstallResult> {
+    let skill_source = resolve_skill_source(checkout_root, resolved_reference)?;
+    let skill_slug = derive_skill_slug(&skill_source)?;
+    if let Some(other) = taken.get(&skill_slug) {
+        return Err(anyhow!(
+            "installs as '{}', the same name as {}",
+            skill_slug,
+            other
+        ));
+    }
+
+    let subpath = skill_source
+        .strip_prefix(checkout_root)
+        .unwrap_or(&skill_source)
+        .to_path_buf();
+    let resolved_path = ResolvedInstallRef {
+        selector: SkillSelector::Path(subpath.clone()),
+        ..resolved_reference.clone()
+    };
+
+    match install_skill_from_checkout(
+        checkout_root,
+        &resolved_path,
+        target_root,
+        existing,
+        prompter,
+    ) {
+        Err(e) if matches!(CapsyncError::find(&e), Some(CapsyncError::Aborted)) => {
+            Ok(InstallResult {
+                installed_path: target_root.join(&skill_slug),
+                skill_slug,
+                replaced_existing: false,
+                skipped_existing: true,
+                source_subpath: subpath,
+            })
+        }
+        other => other,
+    }
+}
+
+/// A directory or `.tar.gz`/`.tgz`/`.zip` archive on this machine used as an
+/// install reference.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalSource {
+    /// Canonical path of the directory or archive.
+    pub path: PathBuf,
+}
+
+impl LocalSource {
+    pub fn is_archive(&self) -> bool {
+        archive::is_archive(&self.path)
+    }
+
+    /// The `file://` URL recorded as `repo_url` in `capsync.lock`. Every byte
+    /// other than unreserved characters and `/` or `:` is percent-encoded, so
+    /// [`resolve_local_source`] reads the same path back.
+    pub fn url(&self) -> String {
+        let path = self.path.to_string_lossy().replace('\\', "/");
+        let mut encoded = String::with_capacity(path.len());
+        for byte in path.bytes() {
+            if byte.is_ascii_alphanumeric() || b"-._~/:".contains(&byte) {
+                encoded.push(byte as char);
+            } else {
+                encoded.push_str(&format!("%{:02X}", byte));
+            }
+        }
+        let path = encoded;
+        if path.starts_with('/') {
+            format!("file://{}", path)
+        } else {
+            format!("file:///{}", path)
+        }
+    }
+}
+
+/// Recognizes references to local files: `file://` URLs, paths starting with
+/// `/`, `./`, `../`, or `~/`, and archive file names. Returns `Ok(None)` for
+/// anything else so remote references resolve as before.
+pub fn resolve_local_source(input: &str) -> Result<Option<LocalSource>> {
+    let trimmed_input = input.trim();
+
+    let path = if let Some(rest) = trimmed_input.strip_prefix("file://") {
+        let rest = rest.strip_prefix("localhost").unwrap_or(rest);
+        if !rest.starts_with('/') {
+            return Err(anyhow!(
+                "file:// references need an absolute path, like file:///path/to/skill"
+            ));
+        }
+        let decoded = rest
+            .split('/')
+            .map(|segment| decode_url_path_component(segment, "file URL"))
+            .collect::<Result<Vec<_>>>()?
+            .join("/");
+        PathBuf::from(decoded)
+    } else if trimmed_input == "."
+        || trimmed_input == ".."
+        || trimmed_input == "~"
+        || ["./", "../", "~/", "/"]
+            .iter()
+            .any(|prefix| trimmed_input.starts_with(prefix))
+        || Path::new(trimmed_input).is_absolute()
+        || (!trimmed_input.contains("://") && archive::is_archive(Path::new(trimmed_input)))
+    {
+        paths::expand_tilde(Path::new(trimmed_input), &paths::home_dir())
+    } else {
+        return Ok(None);
+    };
+
+    if !path.exists() {
+        return Err(anyhow!("No such file or directory: {}", path.display()));
+    }
+    if !path.is_dir() && !archive::is_archive(&path) {
+        return Err(anyhow!(
+            "{} is not a directory or a .tar.gz, .tgz, or .zip archive",
+            path.display()
+        ));
+    }
+
+    let path =
+        fs::canonicalize(&path).with_context(|| format!("Failed to resolve {}", path.display()))?;
+    Ok(Some(LocalSource { path }))
+}
+
+/// The revision to check out: the `@<ref>` on the reference or the one passed
+/// as `--ref`, which must agree when both are given.
+fn pinned_revision(
+    from_reference: Option<String>,
+    requested: Option<&str>,
+) -> Result<Option<String>, CapsyncError> {
+    match (from_reference, requested) {
+        (Some(pinned), Some(requested)) if pinned != requested => {
+            Err(CapsyncError::InvalidReference(format!(
+                "The reference pins '{}' but --ref asks for '{}'",
+                pinned, requested
+            )))
+        }
+        (pinned, requested) => Ok(pinned.or_else(|| requested.map(str::to_string))),
+    }
+}
+
+fn ensure_unpinned(source: &LocalSource, revision: Option<&str>) -> Result<(), CapsyncError> {
+    match revision {
+        Some(revision) => Err(CapsyncError::InvalidReference(format!(
+            "Cannot pin {} to '{}'; --ref only applies to git repositories",
+            source.path.display(),
+            revision
+        ))),
+        None => Ok(()),
+    }
+}
+
+fn ensure_outside_skills_source(source: &LocalSource, skills_source: &Path) -> Result<()> {
+    if let Ok(skills_source) = fs::canonicalize(skills_source) {
+        if source.path.starts_with(&skills_source) {
+            return Err(CapsyncError::InvalidReference(format!(
+                "{} is inside skills_source ({}); it is already installed",
+                source.path.display(),
+                skills_source.display()
+            ))
+            .into());
+        }
+    }
+    Ok(())
+}
+
+/// The one skill in a local directory or extracted archive, relative to its
+/// root. The root itself counts when it holds a `SKILL.md`.
+fn single_skill_subpath(root: &Path) -> Result<PathBuf> {
+    if root.join("SKILL.md").is_file() {
+        return Ok(PathBuf::new());
+    }
+
+    let mut subpaths: Vec<PathBuf> = collect_skill_directories(root)?
+        .into_iter()
+        .filter_map(|dir| dir.strip_prefix(root).ok().map(Path::to_path_buf))
+        .filter(|subpath| {
+            !subpath
+                .components()
+                .any(|part| part.as_os_str().to_string_lossy().starts_with('.'))
+        })
+        .collect();
+    subpaths.sort();
+
+    match subpaths.len() {
+        1 => Ok(subpaths.remove(0)),
+        0 => Err(
+            CapsyncError::SkillNotFound(format!("No SKILL.md found in {}", root.display())).into(),
+        ),
+        count => Err(anyhow!(
+            "Found {} skills ({}). Point at one skill directory, or pass --all or --skill <name>.",
+            count,
+            subpaths
+                .iter()
+                .map(|subpath| subpath.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    }
+}
+
+/// The files an install reads from: a temporary clone, an extracted archive,
+/// or a local directory used in place.
+pub(crate) struct SourceTree {
+    pub(crate) root: PathBuf,
+    local: bool,
+    _temp_dir: Option<TempDir>,
+}
+
+impl SourceTree {
+    /// Clones `repo_url` and, when `revision` is given, checks out that tag
+    /// or commit.
+    pub(crate) fn clone_repository(
+        repo_url: &str,
+        branch: Option<&str>,
+        revision: Option<&str>,
+    ) -> Result<Self> {
+        let temp_dir = tempfile::tempdir().context("Failed to create temporary directory")?;
+        clone_to_path(repo_url, branch.unwrap_or(""), temp_dir.path())?;
+        if let Some(revision) = revision {
+            if checkout_revision(temp_dir.path(), revision)?.is_none() {
+                return Err(CapsyncError::InvalidReference(format!(
+                    "No tag, commit, or branch named '{}' in {}",
+                    revision, repo_url
+                ))
+                .into());
+            }
+        }
+        Ok(Self {
+            root: temp_dir.path().to_path_buf(),
+            local: false,
+            _temp_dir: Some(temp_dir),
+        })
+    }
+
+    pub(crate) fn open_local(source: &LocalSource) -> Result<Self> {
+        if !source.is_archive() {
+            return Ok(Self {
+                root: source.path.clone(),
+                local: true,
+                _temp_dir: None,
+            });
+        }
+
+        let temp_dir = tempfile::tempdir().context("Failed to create temporary directory")?;
+        archive::extract(&source.path, temp_dir.path())?;
+        Ok(Self {
+            root: temp_dir.path().to_path_buf(),
+            local: true,
+            _temp_dir: Some(temp_dir),
+        })
+    }
+
+    /// The version of a skill [`LockedSkill::source_version`] is compared to:
+    /// HEAD for clones, and the hash of the skill's files for local sources,
+    /// which have no commit to point at.
+    pub(crate) fn revision(&self, skill_source: &Path) -> Result<String> {
+        if self.local {
+            hash_skill_tree(skill_source)
+        } else {
+            head_commit(&self.root)
+        }
+    }
+
+    pub(crate) fn locked_skill(
+        &self,
+        reference: &str,
+        resolved_reference: &ResolvedInstallRef,
+        result: &InstallResult,
+    ) -> Result<LockedSkill> {
+        if !self.local {
+            return locked_skill(reference, resolved_reference, &self.root, result);
+        }
+
+        let content_hash = hash_skill_tree(&result.installed_path)?;
+        Ok(LockedSkill {
+            reference: reference.trim().to_string(),
+            repo_url: resolved_reference.repo_url.clone(),
+            branch: None,
+            revision: None,
+            commit: None,
+            source_hash: Some(content_hash.clone()),
+            subpath: result.source_subpath.clone(),
+            content_hash,
+        })
+    }
+}
+
+/// The lockfile entry for a skill just installed from the git checkout at
+/// `checkout_root`.
+pub fn locked_skill(
+    reference: &str,
+    resolved_reference: &ResolvedInstallRef,
+    checkout_root: &Path,
+    result: &InstallResult,
+) -> Result<LockedSkill> {
+    let branch = match &resolved_reference.branch {
+        Some(branch) => Some(branch.clone()),
+        None => head_branch(checkout_root)?,
+    };
+
+    Ok(LockedSkill {
+        reference: reference.trim().to_string(),
+        repo_url: resolved_reference.repo_url.clone(),
+        branch,
+        revision: resolved_reference.revision.clone(),
+        commit: Some(head_commit(checkout_root)?),
+        source_hash: None,
+        subpath: result.source_subpath.clone(),
+        content_hash: hash_skill_tree(&result.installed_path)?,
+    })
 }
 
 pub fn install_skill_from_checkout(
     checkout_root: &Path,
     resolved_reference: &ResolvedInstallRef,
     target_root: &Path,
+    existing: ExistingSkill,
+    prompter: &dyn Prompter,
 ) -> Result<InstallResult> {
     let skill_source = resolve_skill_source(checkout_root, resolved_reference)?;
+    ensure_valid_skill(&skill_source)?;
     let skill_slug = derive_skill_slug(&skill_source)?;
     let target_dir = target_root.join(&skill_slug);
+    let source_subpath = skill_source
+        .strip_prefix(checkout_root)
+        .unwrap_or(&skill_source)
+        .to_path_buf();
 
     fs::create_dir_all(target_root).with_context(|| {
         format!(
@@ -221,7 +1014,21 @@ pub fn install_skill_from_checkout(
     })?;
 
     let replaced_existing = if target_dir.exists() {
-        prompt_replace_existing_skill(&skill_slug, &target_dir)?;
+        match existing {
+            ExistingSkill::Prompt => {
+                confirm_replace_existing_skill(prompter, &skill_slug, &target_dir)?
+            }
+            ExistingSkill::Replace => {}
+            ExistingSkill::Skip => {
+                return Ok(InstallResult {
+                    skill_slug,
+                    installed_path: target_dir,
+                    replaced_existing: false,
+                    skipped_existing: true,
+                    source_subpath,
+                });
+            }
+        }
         true
     } else {
         false
@@ -287,9 +1094,30 @@ pub fn install_skill_from_checkout(
         skill_slug,
         installed_path: target_dir,
         replaced_existing,
+        skipped_existing: false,
+        source_subpath,
     })
 }
 
+/// Refuses skills whose `SKILL.md` has errors. Warnings do not block an install.
+pub(crate) fn ensure_valid_skill(skill_source: &Path) -> Result<()> {
+    let errors: Vec<String> = validate_skill(skill_source)?
+        .into_iter()
+        .filter(|problem| problem.severity == Severity::Error)
+        .map(|problem| problem.to_string())
+        .collect();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(CapsyncError::InvalidSkill {
+            path: skill_source.to_path_buf(),
+            problems: errors,
+        }
+        .into())
+    }
+}
+
 fn ensure_install_root_ready(skills_source: &Path) -> Result<()> {
     if skills_source.exists() && !skills_source.is_dir() {
         return Err(anyhow!(
@@ -308,7 +1136,7 @@ fn ensure_install_root_ready(skills_source: &Path) -> Result<()> {
     Ok(())
 }
 
-fn resolve_skill_source(
+pub(crate) fn resolve_skill_source(
     checkout_root: &Path,
     resolved_reference: &ResolvedInstallRef,
 ) -> Result<PathBuf> {
@@ -361,7 +1189,11 @@ fn resolve_checked_skill_path(checkout_root: &Path, skill_path: &Path) -> Result
 
 fn validate_skill_directory(path: &Path) -> Result<()> {
     if !path.exists() {
-        return Err(anyhow!("Skill path does not exist: {}", path.display()));
+        return Err(CapsyncError::SkillNotFound(format!(
+            "Skill path does not exist: {}",
+            path.display()
+        ))
+        .into());
     }
 
     if !path.is_dir() {
@@ -405,10 +1237,11 @@ fn find_skill_directory_by_slug(checkout_root: &Path, requested_slug: &str) -> R
 
     match matches.len() {
         1 => Ok(matches.remove(0)),
-        0 => Err(anyhow!(
+        0 => Err(CapsyncError::SkillNotFound(format!(
             "Could not find a skill matching '{}' in the resolved repository",
             requested_slug
-        )),
+        ))
+        .into()),
         _ => Err(anyhow!(
             "Found multiple skills matching '{}'. Use an explicit path like owner/repo/path/to/skill or a GitHub tree URL.",
             requested_slug
@@ -416,7 +1249,7 @@ fn find_skill_directory_by_slug(checkout_root: &Path, requested_slug: &str) -> R
     }
 }
 
-fn collect_skill_directories(root: &Path) -> Result<Vec<PathBuf>> {
+pub(crate) fn collect_skill_directories(root: &Path) -> Result<Vec<PathBuf>> {
     let mut skill_directories = Vec::new();
     collect_skill_directories_recursive(root, &mut skill_directories)?;
     Ok(skill_directories)
@@ -451,32 +1284,22 @@ fn collect_skill_directories_recursive(
     Ok(())
 }
 
-fn read_skill_name(skill_markdown_path: &Path) -> Result<Option<String>> {
-    let content = fs::read_to_string(skill_markdown_path)
-        .with_context(|| format!("Failed to read {}", skill_markdown_path.display()))?;
-
-    let mut lines = content.lines();
-    if lines.next() != Some("---") {
-        return Ok(None);
-    }
-
-    for line in lines {
-        if line == "---" {
-            break;
-        }
-
-        if let Some(value) = line.strip_prefix("name:") {
-            return Ok(Some(
-                value
-                    .trim()
-                    .trim_matches('"')
-                    .trim_matches('\'')
-                    .to_string(),
-            ));
-        }
-    }
+pub(crate) fn read_skill_name(skill_markdown_path: &Path) -> Result<Option<String>> {
+    read_frontmatter_field(skill_markdown_path, "name")
+}
 
-    Ok(None)
+/// A top-level string field from a markdown file's frontmatter. Files without
+/// frontmatter, or whose frontmatter does not parse, have no fields.
+pub(crate) fn read_frontmatter_field(markdown_path: &Path, field: &str) -> Result<Option<String>> {
+    let content = fs::read_to_string(markdown_path)
+        .with_context(|| format!("Failed to read {}", markdown_path.display()))?;
+
+    Ok(match Frontmatter::parse(&content) {
+        Ok(Some(frontmatter)) => frontmatter
+            .get_str(field)
+            .map(|value| value.trim().to_string()),
+        _ => None,
+    })
 }
 
 pub fn normalize_skill_slug(input: &str) -> String {
@@ -496,7 +1319,7 @@ pub fn normalize_skill_slug(input: &str) -> String {
     slug.trim_matches('-').to_string()
 }
 
-fn derive_skill_slug(skill_source: &Path) -> Result<String> {
+pub(crate) fn derive_skill_slug(skill_source: &Path) -> Result<String> {
     let skill_markdown_path = skill_source.join("SKILL.md");
 
     if let Some(skill_name) = read_skill_name(&skill_markdown_path)? {
@@ -522,28 +1345,23 @@ fn derive_skill_slug(skill_source: &Path) -> Result<String> {
     Ok(normalized_name)
 }
 
-fn prompt_replace_existing_skill(skill_slug: &str, target_dir: &Path) -> Result<()> {
-    loop {
-        print!(
-            "Skill '{}' already exists at {}. Replace it? [y/N]: ",
-            skill_slug,
-            target_dir.display()
-        );
-        io::stdout().flush()?;
-
-        let mut input = String::new();
-        io::stdin().read_line(&mut input)?;
-        let normalized_input = input.trim().to_lowercase();
-
-        if normalized_input == "y" {
-            return Ok(());
-        }
-
-        if normalized_input.is_empty() || normalized_input == "n" {
-            return Err(anyhow!("Aborted."));
-        }
+fn confirm_replace_existing_skill(
+    prompter: &dyn Prompter,
+    skill_slug: &str,
+    target_dir: &Path,
+) -> Result<()> {
+    let question = format!(
+        "Skill '{}' already exists at {}. Replace it?",
+        skill_slug,
+        target_dir.display()
+    );
 
-        println!("Please enter y or n.");
+    if prompter.confirm(&question, false).map_err(|e| {
+        CapsyncError::from(e).with_flag("--force to replace it or --skip-existing to keep it")
+    })? {
+        Ok(())
+    } else {
+        Err(CapsyncError::Aborted.into())
     }
 }
 
@@ -1,8 +1,20 @@
+pub mod archive;
 pub mod cli;
 pub mod clone;
 pub mod config;
 pub mod detect;
+pub mod doctor;
+pub mod error;
 pub mod git;
 pub mod install;
+pub mod list;
+pub mod lockfile;
+pub mod paths;
+pub mod scaffold;
+pub mod status;
 pub mod sync;
 pub mod tools;
+pub mod ui;
+pub mod uninstall;
+pub mod update;
+pub mod validate;
@@ -0,0 +1,159 @@
+use crate::config::Config;
+use crate::error::CapsyncError;
+use crate::install::{collect_skill_directories, read_frontmatter_field};
+use crate::lockfile::{LockedSkill, Lockfile};
+use crate::sync::SourceKind;
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// Everything `capsync list` reports about the configured sources.
+#[derive(Debug, Clone, Serialize)]
+pub struct Inventory {
+    pub skills_source: PathBuf,
+    pub skills: Vec<InventoryItem>,
+    pub commands_source: Option<PathBuf>,
+    pub commands: Vec<InventoryItem>,
+}
+
+/// A skill directory or command file found in a source.
+#[derive(Debug, Clone, Serialize)]
+pub struct InventoryItem {
+    /// The path relative to its source, with `/` separators.
+    pub slug: String,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub path: PathBuf,
+    /// The `capsync.lock` entry, for skills added with `capsync install`.
+    pub installed_from: Option<LockedSkill>,
+    /// Enabled tools that receive this entry when synced.
+    pub tools: Vec<String>,
+}
+
+/// Lists the skills in `skills_source` and the commands in `commands_source`.
+/// With `tool`, only entries that tool receives are listed.
+pub fn inventory(config: &Config, tool: Option<&str>) -> Result<Inventory, CapsyncError> {
+    if let Some(tool) = tool {
+        if !config.destinations.contains_key(tool) {
+            return Err(CapsyncError::UnknownDestination(tool.to_string()));
+        }
+    }
+
+    if !config.skills_source.exists() {
+        return Err(CapsyncError::SourceMissing {
+            kind: SourceKind::Skills,
+            path: config.skills_source.clone(),
+        });
+    }
+
+    let lockfile = Lockfile::load(&config.skills_source)?;
+    let skill_tools = receiving_tools(config, SourceKind::Skills, tool);
+    let mut skills = Vec::new();
+
+    for skill_dir in collect_skill_directories(&config.skills_source)? {
+        let slug = relative_slug(&config.skills_source, &skill_dir);
+        if slug.split('/').any(|part| part.starts_with('.')) {
+            continue;
+        }
+
+        let skill_markdown = skill_dir.join("SKILL.md");
+        skills.push(InventoryItem {
+            name: read_frontmatter_field(&skill_markdown, "name")?,
+            description: read_frontmatter_field(&skill_markdown, "description")?,
+            installed_from: lockfile.get(&slug).cloned(),
+            tools: skill_tools.clone(),
+            path: skill_dir,
+            slug,
+        });
+    }
+    skills.sort_by(|a, b| a.slug.cmp(&b.slug));
+
+    let commands_source = config
+        .commands_source
+        .clone()
+        .filter(|source| !source.as_os_str().is_empty());
+    let commands = match &commands_source {
+        Some(source) if source.is_dir() => {
+            list_commands(source, receiving_tools(config, SourceKind::Commands, tool))?
+        }
+        _ => Vec::new(),
+    };
+
+    if tool.is_some() && skill_tools.is_empty() {
+        skills.clear();
+    }
+
+    Ok(Inventory {
+        skills_source: config.skills_source.clone(),
+        skills,
+        commands_source,
+        commands,
+    })
+}
+
+fn list_commands(source: &Path, tools: Vec<String>) -> Result<Vec<InventoryItem>> {
+    let mut commands = Vec::new();
+    if tools.is_empty() {
+        return Ok(commands);
+    }
+
+    for entry in fs::read_dir(source)
+        .with_context(|| format!("Failed to read directory {}", source.display()))?
+    {
+        let path = entry?.path();
+        let Some(file_name) = path.file_name().map(|name| name.to_string_lossy()) else {
+            continue;
+        };
+        if file_name.starts_with('.') {
+            continue;
+        }
+
+        let (name, description) = if path.is_file() {
+            (
+                read_frontmatter_field(&path, "name").unwrap_or(None),
+                read_frontmatter_field(&path, "description").unwrap_or(None),
+            )
+        } else {
+            (None, None)
+        };
+
+        commands.push(InventoryItem {
+            slug: file_name.into_owned(),
+            name,
+            description,
+            path,
+            installed_from: None,
+            tools: tools.clone(),
+        });
+    }
+
+    commands.sort_by(|a, b| a.slug.cmp(&b.slug));
+    Ok(commands)
+}
+
+/// Enabled tools that a sync links to this kind of source, sorted by name.
+fn receiving_tools(config: &Config, kind: SourceKind, only: Option<&str>) -> Vec<String> {
+    let mut tools: Vec<String> = config
+        .destinations
+        .iter()
+        .filter(|(_, dest)| dest.enabled)
+        .filter(|(_, dest)| kind == SourceKind::Skills || dest.commands_path.is_some())
+        .filter(|(name, _)| only.is_none_or(|only| only == name.as_str()))
+        .map(|(name, _)| name.clone())
+        .collect();
+    tools.sort();
+    tools
+}
+
+fn relative_slug(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(part) => Some(part.to_string_lossy().into_owned()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
@@ -0,0 +1,153 @@
+use anyhow::{Context, Result};
+use git2::{ObjectType, Oid};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Name of the lockfile `capsync install` keeps in `skills_source`.
+pub const LOCKFILE_NAME: &str = "capsync.lock";
+
+/// Names the algorithm of a [`hash_skill_tree`] hash.
+const HASH_PREFIX: &str = "sha1:";
+
+const LOCKFILE_HEADER: &str =
+    "# Written by 'capsync install'. Records where each installed skill came from.\n\n";
+
+/// Every skill installed with `capsync install`, keyed by slug.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Lockfile {
+    #[serde(default)]
+    pub skills: BTreeMap<String, LockedSkill>,
+}
+
+/// Where an installed skill came from and what was installed.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct LockedSkill {
+    /// The reference as it was passed to `capsync install`.
+    pub reference: String,
+    /// The repository URL, or a `file://` URL for local directories and archives.
+    pub repo_url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    /// The tag or commit the install was pinned to with `@<ref>` or `--ref`.
+    /// `capsync update` stays on it instead of following the branch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revision: Option<String>,
+    /// The commit the skill was copied from. Local directories and archives
+    /// have none.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    /// For local directories and archives, the [`hash_skill_tree`] of the
+    /// source skill when it was installed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_hash: Option<String>,
+    /// The skill directory relative to the repository root.
+    pub subpath: PathBuf,
+    /// [`hash_skill_tree`] of the installed directory.
+    pub content_hash: String,
+}
+
+impl LockedSkill {
+    /// Whether the skill was installed from a local directory or archive
+    /// rather than a git repository.
+    pub fn is_local(&self) -> bool {
+        self.source_hash.is_some()
+    }
+
+    /// What `capsync update` compares against the source: the commit, or the
+    /// source hash for local installs.
+    pub fn source_version(&self) -> &str {
+        self.commit
+            .as_deref()
+            .or(self.source_hash.as_deref())
+            .unwrap_or_default()
+    }
+}
+
+impl Lockfile {
+    pub fn path(skills_source: &Path) -> PathBuf {
+        skills_source.join(LOCKFILE_NAME)
+    }
+
+    /// Reads the lockfile in `skills_source`. A missing file is an empty lockfile.
+    pub fn load(skills_source: &Path) -> Result<Self> {
+        let path = Self::path(skills_source);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        toml::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    pub fn save(&self, skills_source: &Path) -> Result<()> {
+        let path = Self::path(skills_source);
+        let content = format!("{}{}", LOCKFILE_HEADER, toml::to_string_pretty(self)?);
+        fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    pub fn get(&self, slug: &str) -> Option<&LockedSkill> {
+        self.skills.get(slug)
+    }
+
+    pub fn insert(&mut self, slug: &str, skill: LockedSkill) {
+        self.skills.insert(slug.to_string(), skill);
+    }
+
+    pub fn remove(&mut self, slug: &str) -> Option<LockedSkill> {
+        self.skills.remove(slug)
+    }
+}
+
+/// A hash of every file below `root` and its path, skipping `.git`. Two trees
+/// with the same files and contents hash the same regardless of timestamps.
+pub fn hash_skill_tree(root: &Path) -> Result<String> {
+    let mut files = Vec::new();
+    collect_files(root, Path::new(""), &mut files)?;
+    files.sort();
+
+    let mut listing = String::new();
+    for relative in files {
+        let path = root.join(&relative);
+        let content =
+            fs::read(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+        let oid = Oid::hash_object(ObjectType::Blob, &content)?;
+        listing.push_str(&format!("{} {}\n", oid, relative));
+    }
+
+    let oid = Oid::hash_object(ObjectType::Blob, listing.as_bytes())?;
+    Ok(format!("{}{}", HASH_PREFIX, oid))
+}
+
+/// Shortens a commit or a [`hash_skill_tree`] hash for display.
+pub fn short_version(version: &str) -> &str {
+    let version = version.strip_prefix(HASH_PREFIX).unwrap_or(version);
+    &version[..version.len().min(7)]
+}
+
+fn collect_files(root: &Path, relative: &Path, files: &mut Vec<String>) -> Result<()> {
+    let path = root.join(relative);
+    for entry in fs::read_dir(&path)
+        .with_context(|| format!("Failed to read directory {}", path.display()))?
+    {
+        let entry = entry?;
+        if entry.file_name() == ".git" {
+            continue;
+        }
+
+        let entry_relative = relative.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            collect_files(root, &entry_relative, files)?;
+        } else {
+            let components: Vec<_> = entry_relative
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect();
+            files.push(components.join("/"));
+        }
+    }
+
+    Ok(())
+}
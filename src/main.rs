@@ -1,5 +1,5 @@
-use anyhow::Result;
+use std::process::ExitCode;
 
-fn main() -> Result<()> {
+fn main() -> ExitCode {
     capsync::cli::run()
 }
@@ -0,0 +1,110 @@
+use anyhow::{Result, anyhow};
+use std::env;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Set from `--home`. Takes precedence over `CAPSYNC_HOME`.
+static HOME_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+/// Set from `--config`. Takes precedence over `CAPSYNC_CONFIG`.
+static CONFIG_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+/// Points CapSync at another home directory for the rest of the process.
+/// Only the first call has an effect.
+pub fn set_home_override(home: PathBuf) {
+    let _ = HOME_OVERRIDE.set(home);
+}
+
+/// Points CapSync at another config file for the rest of the process.
+/// Only the first call has an effect.
+pub fn set_config_override(config: PathBuf) {
+    let _ = CONFIG_OVERRIDE.set(config);
+}
+
+/// The home directory tool paths are resolved against: `--home`, then
+/// `CAPSYNC_HOME`, then the user's real home directory.
+pub fn home_dir() -> PathBuf {
+    explicit_home().unwrap_or_else(|| dirs::home_dir().unwrap_or_else(|| PathBuf::from("~")))
+}
+
+fn explicit_home() -> Option<PathBuf> {
+    HOME_OVERRIDE
+        .get()
+        .cloned()
+        .or_else(|| non_empty_env("CAPSYNC_HOME"))
+}
+
+/// The config file: `--config`, then `CAPSYNC_CONFIG`, then
+/// `$XDG_CONFIG_HOME/capsync/config.toml`, then `~/.config/capsync/config.toml`.
+pub fn config_path() -> PathBuf {
+    let explicit = CONFIG_OVERRIDE
+        .get()
+        .cloned()
+        .or_else(|| non_empty_env("CAPSYNC_CONFIG"));
+
+    // An overridden home stays self-contained, so XDG_CONFIG_HOME from the
+    // real environment is ignored.
+    let xdg_config_home = match explicit_home() {
+        Some(_) => None,
+        None => non_empty_env("XDG_CONFIG_HOME"),
+    };
+
+    resolve_config_path(explicit, xdg_config_home, &home_dir())
+}
+
+/// Picks the config file from an explicit path, `XDG_CONFIG_HOME`, or the home directory.
+pub fn resolve_config_path(
+    explicit: Option<PathBuf>,
+    xdg_config_home: Option<PathBuf>,
+    home: &Path,
+) -> PathBuf {
+    if let Some(path) = explicit {
+        return path;
+    }
+
+    xdg_config_home
+        .unwrap_or_else(|| home.join(".config"))
+        .join("capsync/config.toml")
+}
+
+/// The optional user tool registry, stored next to the config file.
+pub fn registry_path() -> PathBuf {
+    config_path()
+        .parent()
+        .map(|dir| dir.join("tools.toml"))
+        .unwrap_or_else(|| PathBuf::from("tools.toml"))
+}
+
+/// User templates for `capsync new`, stored next to the config file.
+pub fn templates_dir() -> PathBuf {
+    config_path()
+        .parent()
+        .map(|dir| dir.join("templates"))
+        .unwrap_or_else(|| PathBuf::from("templates"))
+}
+
+/// Expands `~` against [`home_dir`] and `$VARS` from the environment.
+pub fn expand(input: &str) -> Result<PathBuf> {
+    let home = home_dir();
+    let expanded = shellexpand::full_with_context(
+        input,
+        || Some(home.to_string_lossy().into_owned()),
+        |var| env::var(var).map(Some),
+    )
+    .map_err(|e| anyhow!("Failed to expand path: {}", e))?;
+
+    Ok(PathBuf::from(expanded.as_ref()))
+}
+
+/// Expands a leading `~` against `home`, leaving other paths unchanged.
+pub fn expand_tilde(path: &Path, home: &Path) -> PathBuf {
+    match path.strip_prefix("~") {
+        Ok(rest) => home.join(rest),
+        Err(_) => path.to_path_buf(),
+    }
+}
+
+fn non_empty_env(name: &str) -> Option<PathBuf> {
+    env::var_os(name)
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+}
@@ -0,0 +1,332 @@
+use crate::config::Config;
+use crate::error::CapsyncError;
+use crate::install::{ensure_valid_skill, normalize_skill_slug};
+use crate::sync::SourceKind;
+use anyhow::{Context, Result, anyhow};
+use std::fs;
+use std::path::{Path, PathBuf};
+use yaml_rust2::{Yaml, YamlLoader};
+
+const BUILTIN_SKILL_TEMPLATE: &str = include_str!("templates/skill.md");
+const BUILTIN_COMMAND_TEMPLATE: &str = include_str!("templates/command.md");
+
+pub const DEFAULT_TEMPLATE: &str = "default";
+
+#[derive(Debug, Clone)]
+pub struct NewOptions {
+    pub kind: SourceKind,
+    /// The name as typed; the slug is derived with [`normalize_skill_slug`].
+    pub name: String,
+    pub description: Option<String>,
+    /// Template name, [`DEFAULT_TEMPLATE`] when not given.
+    pub template: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct NewResult {
+    pub slug: String,
+    /// The skill directory or command file that was created.
+    pub path: PathBuf,
+    pub template: String,
+}
+
+/// Where a template comes from. User templates live in `templates_dir` as
+/// `skills/<template>/` directories holding a `SKILL.md`, and as
+/// `commands/<template>.md` files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Template {
+    Builtin(&'static str),
+    SkillDirectory(PathBuf),
+    CommandFile(PathBuf),
+}
+
+/// Template names available for `kind`, built-in and user ones, sorted.
+pub fn template_names(kind: SourceKind, templates_dir: &Path) -> Result<Vec<String>> {
+    let mut names = vec![DEFAULT_TEMPLATE.to_string()];
+
+    let dir = user_templates_dir(kind, templates_dir);
+    if dir.is_dir() {
+        for entry in fs::read_dir(&dir)
+            .with_context(|| format!("Failed to read directory {}", dir.display()))?
+        {
+            let path = entry?.path();
+            let name = match kind {
+                SourceKind::Skills if path.join("SKILL.md").is_file() => path.file_name(),
+                SourceKind::Commands
+                    if path.is_file() && path.extension().is_some_and(|ext| ext == "md") =>
+                {
+                    path.file_stem()
+                }
+                _ => None,
+            };
+            if let Some(name) = name.map(|name| name.to_string_lossy().into_owned()) {
+                if !name.starts_with('.') && !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+        }
+    }
+
+    names.sort();
+    Ok(names)
+}
+
+/// Creates a skill directory in `skills_source` or a command file in
+/// `commands_source` from a template. Placeholders `{{name}}` (the slug),
+/// `{{title}}`, and `{{description}}` are filled in; inside the frontmatter
+/// values are quoted when YAML needs it. New skills must pass
+/// [`crate::validate::validate_skill`] or nothing is left behind.
+pub fn create(
+    options: &NewOptions,
+    config: &Config,
+    templates_dir: &Path,
+) -> Result<NewResult, CapsyncError> {
+    let slug = normalize_skill_slug(&options.name);
+    if slug.is_empty() {
+        return Err(anyhow!(
+            "Cannot make a name from '{}'; use letters or digits",
+            options.name
+        )
+        .into());
+    }
+
+    let template_name = options
+        .template
+        .clone()
+        .unwrap_or_else(|| DEFAULT_TEMPLATE.to_string());
+    let template = find_template(options.kind, &template_name, templates_dir)?;
+
+    let title = if options.name.trim() == slug {
+        title_from_slug(&slug)
+    } else {
+        options.name.trim().to_string()
+    };
+    let description = options
+        .description
+        .clone()
+        .unwrap_or_else(|| match options.kind {
+            SourceKind::Skills => format!("Describe what {} does and when to use it", title),
+            SourceKind::Commands => format!("Describe what {} does", title),
+        });
+    let values = [
+        ("name", slug.as_str()),
+        ("title", title.as_str()),
+        ("description", description.as_str()),
+    ];
+
+    let path = match options.kind {
+        SourceKind::Skills => {
+            let skill_dir =
+                source_dir(SourceKind::Skills, Some(&config.skills_source))?.join(&slug);
+            ensure_free(&skill_dir, "Skill", &slug)?;
+            create_skill(&template, &skill_dir, &values)?;
+            skill_dir
+        }
+        SourceKind::Commands => {
+            let command_file = source_dir(SourceKind::Commands, config.commands_source.as_ref())?
+                .join(format!("{}.md", slug));
+            ensure_free(&command_file, "Command", &slug)?;
+            let content = match &template {
+                Template::Builtin(content) => content.to_string(),
+                Template::CommandFile(path) => fs::read_to_string(path)
+                    .with_context(|| format!("Failed to read template {}", path.display()))?,
+                Template::SkillDirectory(_) => unreachable!("skill templates are not commands"),
+            };
+            fs::write(&command_file, render(&content, &values))
+                .with_context(|| format!("Failed to write {}", command_file.display()))?;
+            command_file
+        }
+    };
+
+    Ok(NewResult {
+        slug,
+        path,
+        template: template_name,
+    })
+}
+
+fn user_templates_dir(kind: SourceKind, templates_dir: &Path) -> PathBuf {
+    match kind {
+        SourceKind::Skills => templates_dir.join("skills"),
+        SourceKind::Commands => templates_dir.join("commands"),
+    }
+}
+
+/// Looks `name` up in the user templates first, so a user `default` replaces
+/// the built-in one.
+fn find_template(kind: SourceKind, name: &str, templates_dir: &Path) -> Result<Template> {
+    let dir = user_templates_dir(kind, templates_dir);
+    match kind {
+        SourceKind::Skills if dir.join(name).join("SKILL.md").is_file() => {
+            return Ok(Template::SkillDirectory(dir.join(name)));
+        }
+        SourceKind::Commands if dir.join(format!("{}.md", name)).is_file() => {
+            return Ok(Template::CommandFile(dir.join(format!("{}.md", name))));
+        }
+        _ => {}
+    }
+
+    if name == DEFAULT_TEMPLATE {
+        return Ok(Template::Builtin(match kind {
+            SourceKind::Skills => BUILTIN_SKILL_TEMPLATE,
+            SourceKind::Commands => BUILTIN_COMMAND_TEMPLATE,
+        }));
+    }
+
+    let label = match kind {
+        SourceKind::Skills => "skill",
+        SourceKind::Commands => "command",
+    };
+    Err(anyhow!(
+        "Unknown {} template '{}'. Available: {} (user templates are read from {})",
+        label,
+        name,
+        template_names(kind, templates_dir)?.join(", "),
+        dir.display()
+    ))
+}
+
+fn source_dir(kind: SourceKind, source: Option<&PathBuf>) -> Result<PathBuf, CapsyncError> {
+    let Some(source) = source.filter(|source| !source.as_os_str().is_empty()) else {
+        let field = match kind {
+            SourceKind::Skills => "skills_source",
+            SourceKind::Commands => "commands_source",
+        };
+        return Err(CapsyncError::ConfigInvalid(format!(
+            "No {} is configured. Set it in the config first.",
+            field
+        )));
+    };
+
+    if !source.is_dir() {
+        return Err(CapsyncError::SourceMissing {
+            kind,
+            path: source.clone(),
+        });
+    }
+
+    Ok(source.clone())
+}
+
+fn ensure_free(path: &Path, label: &str, slug: &str) -> Result<()> {
+    if path.exists() || path.is_symlink() {
+        return Err(anyhow!(
+            "{} '{}' already exists at {}",
+            label,
+            slug,
+            path.display()
+        ));
+    }
+    Ok(())
+}
+
+fn create_skill(template: &Template, skill_dir: &Path, values: &[(&str, &str)]) -> Result<()> {
+    let created = match template {
+        Template::Builtin(content) => fs::create_dir_all(skill_dir)
+            .and_then(|()| fs::write(skill_dir.join("SKILL.md"), render(content, values)))
+            .with_context(|| format!("Failed to create {}", skill_dir.display())),
+        Template::SkillDirectory(template_dir) => copy_template(template_dir, skill_dir, values),
+        Template::CommandFile(_) => unreachable!("command templates are not skills"),
+    }
+    .and_then(|()| ensure_valid_skill(skill_dir));
+
+    if created.is_err() {
+        let _ = fs::remove_dir_all(skill_dir);
+    }
+    created
+}
+
+/// Copies a skill template directory, filling in placeholders in every text
+/// file. Hidden entries such as `.git` are skipped.
+fn copy_template(source: &Path, destination: &Path, values: &[(&str, &str)]) -> Result<()> {
+    fs::create_dir_all(destination)
+        .with_context(|| format!("Failed to create directory {}", destination.display()))?;
+
+    for entry in fs::read_dir(source)
+        .with_context(|| format!("Failed to read directory {}", source.display()))?
+    {
+        let entry = entry?;
+        if entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+
+        let from = entry.path();
+        let to = destination.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_template(&from, &to, values)?;
+            continue;
+        }
+
+        let bytes =
+            fs::read(&from).with_context(|| format!("Failed to read {}", from.display()))?;
+        let bytes = match String::from_utf8(bytes) {
+            Ok(text) => render(&text, values).into_bytes(),
+            Err(e) => e.into_bytes(),
+        };
+        fs::write(&to, bytes).with_context(|| format!("Failed to write {}", to.display()))?;
+    }
+
+    Ok(())
+}
+
+/// Replaces `{{key}}` placeholders. Values in the leading frontmatter are
+/// written as YAML scalars so a description with a colon still parses.
+fn render(content: &str, values: &[(&str, &str)]) -> String {
+    let mut rendered = String::with_capacity(content.len());
+    let mut in_frontmatter = false;
+
+    for (index, line) in content.split_inclusive('\n').enumerate() {
+        let is_fence = line.trim_end() == "---";
+        if index == 0 && is_fence {
+            in_frontmatter = true;
+        } else if in_frontmatter && is_fence {
+            in_frontmatter = false;
+        }
+
+        let mut line = line.to_string();
+        for (key, value) in values {
+            let placeholder = format!("{{{{{}}}}}", key);
+            if line.contains(&placeholder) {
+                let value = if in_frontmatter {
+                    yaml_scalar(value)
+                } else {
+                    value.to_string()
+                };
+                line = line.replace(&placeholder, &value);
+            }
+        }
+        rendered.push_str(&line);
+    }
+
+    rendered
+}
+
+/// `value` as written when it reads back as the same YAML string, and
+/// double-quoted otherwise, so names like `123`, `null`, or `true` stay strings.
+fn yaml_scalar(value: &str) -> String {
+    let plain = value.starts_with(|c: char| c.is_ascii_alphanumeric())
+        && value
+            .chars()
+            .all(|c| c.is_alphanumeric() || " -_.,()".contains(c))
+        && !value.ends_with(' ')
+        && YamlLoader::load_from_str(value)
+            .is_ok_and(|documents| documents == [Yaml::String(value.to_string())]);
+    if plain {
+        value.to_string()
+    } else {
+        serde_json::to_string(value).unwrap_or_else(|_| format!("{:?}", value))
+    }
+}
+
+fn title_from_slug(slug: &str) -> String {
+    slug.split('-')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
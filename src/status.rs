@@ -0,0 +1,289 @@
+use crate::config::{Config, CustomToolConfig, LinkMode};
+use crate::sync::{MirrorStatus, PerSkillStatus, SourceKind, SyncManager};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Everything `capsync status` reports, in a form that can be printed or
+/// serialized as JSON.
+#[derive(Debug, Clone, Serialize)]
+pub struct StatusReport {
+    pub config_path: PathBuf,
+    pub skills_source: SourceState,
+    pub commands_source: Option<SourceState>,
+    pub destinations: Vec<DestinationStatus>,
+    /// Whether every enabled destination matches its source.
+    pub in_sync: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SourceState {
+    pub path: PathBuf,
+    pub exists: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DestinationStatus {
+    pub name: String,
+    pub enabled: bool,
+    pub link_mode: LinkMode,
+    pub in_sync: bool,
+    pub skills: PathStatus,
+    pub commands: Option<PathStatus>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PathStatus {
+    pub path: PathBuf,
+    #[serde(flatten)]
+    pub state: LinkState,
+}
+
+/// What is found at a destination path, judged against its link mode.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "state", rename_all = "kebab-case")]
+pub enum LinkState {
+    /// A symlink to the configured source.
+    Linked {
+        target: PathBuf,
+    },
+    /// A working symlink that points somewhere other than the configured source.
+    LinkedElsewhere {
+        target: PathBuf,
+    },
+    /// A symlink whose target does not exist.
+    Broken {
+        target: PathBuf,
+    },
+    /// A symlink that could not be read.
+    Unreadable {
+        error: String,
+    },
+    /// A real directory where a symlink is expected.
+    ForeignDirectory,
+    /// A regular file where a symlink is expected.
+    ForeignFile,
+    /// Nothing there yet.
+    Missing,
+    /// A whole-directory symlink where per-skill links or copies are expected.
+    WholeDirectoryLink {
+        target: PathBuf,
+    },
+    /// No source is configured to compare against.
+    NoSource,
+    PerSkill(PerSkillStatus),
+    Mirror(MirrorStatus),
+    /// The destination could not be inspected.
+    Error {
+        error: String,
+    },
+}
+
+impl LinkState {
+    /// Whether the destination already matches what `capsync sync` would make it.
+    pub fn is_in_sync(&self) -> bool {
+        match self {
+            LinkState::Linked { .. } | LinkState::NoSource => true,
+            LinkState::PerSkill(status) => status.is_in_sync(),
+            LinkState::Mirror(status) => status.is_in_sync(),
+            _ => false,
+        }
+    }
+}
+
+impl StatusReport {
+    /// Enabled destinations that do not match their source.
+    pub fn out_of_sync(&self) -> impl Iterator<Item = &DestinationStatus> {
+        self.destinations
+            .iter()
+            .filter(|dest| dest.enabled && !dest.in_sync)
+    }
+}
+
+/// The configuration as `capsync config` shows it.
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigReport {
+    pub config_path: PathBuf,
+    pub skills_source: PathBuf,
+    pub commands_source: Option<PathBuf>,
+    pub destinations: Vec<ConfiguredDestination>,
+    pub custom_tools: BTreeMap<String, CustomToolConfig>,
+    pub default_host: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfiguredDestination {
+    pub name: String,
+    pub enabled: bool,
+    pub skills_path: PathBuf,
+    pub commands_path: Option<PathBuf>,
+    pub link_mode: LinkMode,
+}
+
+pub fn status_report(config: &Config, config_path: &Path) -> StatusReport {
+    let commands_source = config
+        .commands_source
+        .as_deref()
+        .filter(|source| !source.as_os_str().is_empty());
+
+    let mut destinations: Vec<_> = config
+        .destinations
+        .iter()
+        .map(|(name, dest)| {
+            let skills = path_status(
+                Some(&config.skills_source),
+                &dest.skills_path,
+                dest.link_mode,
+                SourceKind::Skills,
+            );
+            let commands = dest.commands_path.as_ref().map(|commands_path| {
+                path_status(
+                    commands_source,
+                    commands_path,
+                    dest.link_mode,
+                    SourceKind::Commands,
+                )
+            });
+            let in_sync = skills.state.is_in_sync()
+                && commands
+                    .as_ref()
+                    .is_none_or(|commands| commands.state.is_in_sync());
+
+            DestinationStatus {
+                name: name.clone(),
+                enabled: dest.enabled,
+                link_mode: dest.link_mode,
+                in_sync,
+                skills,
+                commands,
+            }
+        })
+        .collect();
+    destinations.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let in_sync = destinations
+        .iter()
+        .all(|dest| !dest.enabled || dest.in_sync);
+
+    StatusReport {
+        config_path: config_path.to_path_buf(),
+        skills_source: source_state(&config.skills_source),
+        commands_source: config.commands_source.as_deref().map(source_state),
+        destinations,
+        in_sync,
+    }
+}
+
+pub fn config_report(config: &Config, config_path: &Path) -> ConfigReport {
+    let mut destinations: Vec<_> = config
+        .destinations
+        .iter()
+        .map(|(name, dest)| ConfiguredDestination {
+            name: name.clone(),
+            enabled: dest.enabled,
+            skills_path: dest.skills_path.clone(),
+            commands_path: dest.commands_path.clone(),
+            link_mode: dest.link_mode,
+        })
+        .collect();
+    destinations.sort_by(|a, b| a.name.cmp(&b.name));
+
+    ConfigReport {
+        config_path: config_path.to_path_buf(),
+        skills_source: config.skills_source.clone(),
+        commands_source: config.commands_source.clone(),
+        destinations,
+        custom_tools: config.custom_tools.clone(),
+        default_host: config.install_host().to_string(),
+    }
+}
+
+fn source_state(path: &Path) -> SourceState {
+    SourceState {
+        path: path.to_path_buf(),
+        exists: path.exists(),
+    }
+}
+
+/// Inspects one destination path. `source` is `None` when no source is configured.
+pub fn path_status(
+    source: Option<&Path>,
+    path: &Path,
+    link_mode: LinkMode,
+    kind: SourceKind,
+) -> PathStatus {
+    let state = match link_mode {
+        LinkMode::Symlink => symlink_state(source, path),
+        LinkMode::PerSkill | LinkMode::Copy | LinkMode::Hardlink => match source {
+            None => LinkState::NoSource,
+            Some(_) if path.is_symlink() => LinkState::WholeDirectoryLink {
+                target: fs::read_link(path).unwrap_or_default(),
+            },
+            Some(_) if !path.exists() => LinkState::Missing,
+            Some(source) if link_mode == LinkMode::PerSkill => {
+                match SyncManager::per_skill_status(source, path, kind) {
+                    Ok(status) => LinkState::PerSkill(status),
+                    Err(e) => LinkState::Error {
+                        error: e.to_string(),
+                    },
+                }
+            }
+            Some(source) => match SyncManager::mirror_status(source, path, kind, link_mode) {
+                Ok(status) => LinkState::Mirror(status),
+                Err(e) => LinkState::Error {
+                    error: e.to_string(),
+                },
+            },
+        },
+    };
+
+    PathStatus {
+        path: path.to_path_buf(),
+        state,
+    }
+}
+
+fn symlink_state(source: Option<&Path>, path: &Path) -> LinkState {
+    if path.is_symlink() {
+        let target = match fs::read_link(path) {
+            Ok(target) => target,
+            Err(e) => {
+                return LinkState::Unreadable {
+                    error: e.to_string(),
+                };
+            }
+        };
+        // Relative link targets are resolved against the link's own directory.
+        let resolved = match path.parent() {
+            Some(parent) => parent.join(&target),
+            None => target.clone(),
+        };
+
+        return if !resolved.exists() {
+            LinkState::Broken { target }
+        } else if source.is_none_or(|source| same_path(source, &resolved)) {
+            LinkState::Linked { target }
+        } else {
+            LinkState::LinkedElsewhere { target }
+        };
+    }
+
+    if path.is_dir() {
+        LinkState::ForeignDirectory
+    } else if path.exists() {
+        LinkState::ForeignFile
+    } else if source.is_none() {
+        LinkState::NoSource
+    } else {
+        LinkState::Missing
+    }
+}
+
+fn same_path(a: &Path, b: &Path) -> bool {
+    a == b
+        || matches!(
+            (fs::canonicalize(a), fs::canonicalize(b)),
+            (Ok(a), Ok(b)) if a == b
+        )
+}
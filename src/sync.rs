@@ -1,120 +1,1207 @@
-use crate::config::Config;
+use crate::config::{Config, DestinationConfig, LinkMode};
+use crate::error::CapsyncError;
+use crate::ui::{Reporter, Terminal};
 use anyhow::{Context, Result, anyhow};
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::ffi::OsString;
+use std::fmt;
 use std::fs;
-use std::path::Path;
+use std::path::{Component, Path, PathBuf};
+use std::time::SystemTime;
+
+/// Lists the files CapSync wrote into a copy or hardlink destination, so later
+/// syncs and removals never touch files the tool created itself.
+pub const MIRROR_MANIFEST: &str = ".capsync-manifest";
 
 pub struct SyncManager;
 
+/// Enabled destinations that resolve to the same directory.
+struct PathGroup<'a> {
+    path: &'a Path,
+    resolved: PathBuf,
+    members: Vec<(&'a String, LinkMode)>,
+}
+
+/// Which configured source a destination is synced from. In per-skill mode
+/// only skill directories are linked, while every command entry is linked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceKind {
+    Skills,
+    Commands,
+}
+
+/// Per-entry link state of a destination that uses `link_mode = "per-skill"`.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PerSkillStatus {
+    pub linked: Vec<String>,
+    pub missing: Vec<String>,
+    pub conflicts: Vec<String>,
+    pub stale: Vec<String>,
+}
+
+impl PerSkillStatus {
+    pub fn is_in_sync(&self) -> bool {
+        self.missing.is_empty() && self.conflicts.is_empty() && self.stale.is_empty()
+    }
+}
+
+/// Drift between the source tree and a copy or hardlink destination.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MirrorStatus {
+    /// Source files whose destination copy differs.
+    pub changed: Vec<PathBuf>,
+    /// Source files that have not been copied yet.
+    pub missing: Vec<PathBuf>,
+    /// Previously copied files that no longer exist in the source.
+    pub stale: Vec<PathBuf>,
+    /// Top-level entries that exist in the destination but were not created by CapSync.
+    pub conflicts: Vec<String>,
+}
+
+impl MirrorStatus {
+    pub fn is_in_sync(&self) -> bool {
+        self.changed.is_empty() && self.missing.is_empty() && self.stale.is_empty()
+    }
+}
+
+/// What `adopt` does with a destination that is a real directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AdoptStrategy {
+    /// Move each entry into the source, renaming entries whose name is taken.
+    MoveToSource,
+    /// Move the whole destination aside to a timestamped backup.
+    Backup,
+}
+
+/// A single filesystem change that a sync or remove would make.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlannedAction {
+    CreateDirectory {
+        path: PathBuf,
+    },
+    CreateLink {
+        source: PathBuf,
+        dest: PathBuf,
+    },
+    ReplaceLink {
+        dest: PathBuf,
+        old_target: PathBuf,
+        source: PathBuf,
+    },
+    RemoveLink {
+        path: PathBuf,
+    },
+    /// Removes a directory only if it is empty, so nothing unexpected is lost.
+    RemoveEmptyDirectory {
+        path: PathBuf,
+    },
+    DeleteFile {
+        path: PathBuf,
+    },
+    /// Deletes a directory and everything in it.
+    DeleteDirectory {
+        path: PathBuf,
+    },
+    CopyFile {
+        source: PathBuf,
+        dest: PathBuf,
+    },
+    HardlinkFile {
+        source: PathBuf,
+        dest: PathBuf,
+    },
+    /// Removes a previously mirrored file and any directories it leaves empty.
+    RemoveCopiedFile {
+        root: PathBuf,
+        relative: PathBuf,
+    },
+    WriteManifest {
+        root: PathBuf,
+        files: BTreeSet<PathBuf>,
+    },
+    Move {
+        from: PathBuf,
+        to: PathBuf,
+    },
+    Skip {
+        path: PathBuf,
+        reason: String,
+    },
+}
+
+impl fmt::Display for PlannedAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlannedAction::CreateDirectory { path } => {
+                write!(f, "create directory {}", path.display())
+            }
+            PlannedAction::CreateLink { source, dest } => {
+                write!(
+                    f,
+                    "create symlink {} -> {}",
+                    dest.display(),
+                    source.display()
+                )
+            }
+            PlannedAction::ReplaceLink {
+                dest,
+                old_target,
+                source,
+            } => write!(
+                f,
+                "replace symlink {} (currently -> {}) with -> {}",
+                dest.display(),
+                old_target.display(),
+                source.display()
+            ),
+            PlannedAction::RemoveLink { path } => write!(f, "remove symlink {}", path.display()),
+            PlannedAction::RemoveEmptyDirectory { path } => {
+                write!(f, "remove empty directory {}", path.display())
+            }
+            PlannedAction::DeleteFile { path } => write!(f, "delete file {}", path.display()),
+            PlannedAction::DeleteDirectory { path } => {
+                write!(f, "delete directory {} and its contents", path.display())
+            }
+            PlannedAction::CopyFile { source, dest } => {
+                write!(f, "copy {} to {}", source.display(), dest.display())
+            }
+            PlannedAction::HardlinkFile { source, dest } => {
+                write!(f, "hard-link {} to {}", source.display(), dest.display())
+            }
+            PlannedAction::RemoveCopiedFile { root, relative } => {
+                write!(f, "remove copied file {}", root.join(relative).display())
+            }
+            PlannedAction::WriteManifest { root, files } => write!(
+                f,
+                "record {} file(s) in {}",
+                files.len(),
+                root.join(MIRROR_MANIFEST).display()
+            ),
+            PlannedAction::Move { from, to } => {
+                write!(f, "move {} to {}", from.display(), to.display())
+            }
+            PlannedAction::Skip { path, reason } => {
+                write!(f, "skip {} ({})", path.display(), reason)
+            }
+        }
+    }
+}
+
+/// The actions planned for one destination path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlanEntry {
+    pub name: String,
+    pub path: PathBuf,
+    pub actions: Vec<PlannedAction>,
+    /// A problem found while planning. It is reported as an error once the
+    /// planned actions have been applied.
+    pub error: Option<String>,
+}
+
+impl PlanEntry {
+    fn new(name: &str, path: &Path) -> Self {
+        Self {
+            name: name.to_string(),
+            path: path.to_path_buf(),
+            actions: Vec::new(),
+            error: None,
+        }
+    }
+
+    pub fn has_changes(&self) -> bool {
+        self.actions
+            .iter()
+            .any(|action| !matches!(action, PlannedAction::Skip { .. }))
+    }
+}
+
+/// Everything a sync or remove would do, built without touching the filesystem.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyncPlan {
+    pub entries: Vec<PlanEntry>,
+}
+
+impl SyncPlan {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn merge(&mut self, other: SyncPlan) {
+        self.entries.extend(other.entries);
+    }
+
+    pub fn has_changes(&self) -> bool {
+        self.entries.iter().any(PlanEntry::has_changes)
+    }
+
+    pub fn print(&self) {
+        self.report(&Terminal);
+    }
+
+    pub fn report(&self, reporter: &dyn Reporter) {
+        if self.entries.is_empty() {
+            reporter.info("\nNothing to do.");
+            return;
+        }
+
+        reporter.info("\nPlanned actions:");
+        for entry in &self.entries {
+            reporter.info(&format!("  {}: {}", entry.name, entry.path.display()));
+
+            if entry.actions.is_empty() && entry.error.is_none() {
+                reporter.info("    - nothing to do");
+            }
+
+            for action in &entry.actions {
+                reporter.info(&format!("    - {action}"));
+            }
+
+            if let Some(error) = &entry.error {
+                reporter.info(&format!("    ! {error}"));
+            }
+        }
+    }
+}
+
 impl SyncManager {
-    pub fn sync_skills(config: &Config) -> Result<SyncResult> {
-        let mut result = SyncResult::new();
+    pub fn sync_skills(config: &Config) -> Result<SyncResult, CapsyncError> {
+        Ok(Self::apply(&Self::plan_skills(config)?))
+    }
+
+    pub fn sync_commands(config: &Config) -> Result<SyncResult, CapsyncError> {
+        Ok(Self::apply(&Self::plan_commands(config)?))
+    }
+
+    pub fn sync_all(config: &Config) -> Result<SyncResult, CapsyncError> {
+        Ok(Self::apply(&Self::plan_sync(config)?))
+    }
+
+    pub fn plan_skills(config: &Config) -> Result<SyncPlan, CapsyncError> {
+        let mut plan = SyncPlan::new();
 
         if !config.skills_source.exists() {
-            return Err(anyhow!(
-                "Skills source directory does not exist: {}",
-                config.skills_source.display()
-            ));
+            return Err(CapsyncError::SourceMissing {
+                kind: SourceKind::Skills,
+                path: config.skills_source.clone(),
+            });
         }
 
-        for (name, dest_config) in &config.destinations {
-            if dest_config.enabled {
-                match Self::sync_destination(&config.skills_source, &dest_config.skills_path) {
-                    Ok(()) => result.add_success(name),
-                    Err(e) => result.add_error(name, e.to_string()),
-                }
-            }
+        let destinations = Self::enabled_destinations(config)
+            .into_iter()
+            .map(|(name, dest_config)| (name, &dest_config.skills_path, dest_config.link_mode));
+        for group in Self::group_by_path(destinations) {
+            plan.entries.push(Self::plan_group(
+                &group,
+                "",
+                &config.skills_source,
+                SourceKind::Skills,
+            ));
         }
 
-        Ok(result)
+        Ok(plan)
     }
 
-    pub fn sync_commands(config: &Config) -> Result<SyncResult> {
-        let mut result = SyncResult::new();
+    pub fn plan_commands(config: &Config) -> Result<SyncPlan, CapsyncError> {
+        let mut plan = SyncPlan::new();
 
         let commands_source = match &config.commands_source {
             Some(source) if !source.as_os_str().is_empty() => source,
-            _ => return Ok(result),
+            _ => return Ok(plan),
         };
 
         if !commands_source.exists() {
+            return Err(CapsyncError::SourceMissing {
+                kind: SourceKind::Commands,
+                path: commands_source.clone(),
+            });
+        }
+
+        let destinations =
+            Self::enabled_destinations(config)
+                .into_iter()
+                .filter_map(|(name, dest_config)| {
+                    let commands_path = dest_config.commands_path.as_ref()?;
+                    Some((name, commands_path, dest_config.link_mode))
+                });
+        for group in Self::group_by_path(destinations) {
+            plan.entries.push(Self::plan_group(
+                &group,
+                " (commands)",
+                commands_source,
+                SourceKind::Commands,
+            ));
+        }
+
+        Ok(plan)
+    }
+
+    pub fn plan_sync(config: &Config) -> Result<SyncPlan, CapsyncError> {
+        let mut plan = Self::plan_skills(config)?;
+
+        if config.has_commands() {
+            plan.merge(Self::plan_commands(config)?);
+        }
+
+        Ok(plan)
+    }
+
+    /// Applies a plan, continuing with the remaining destinations when one fails.
+    pub fn apply(plan: &SyncPlan) -> SyncResult {
+        let mut result = SyncResult::new();
+
+        'entries: for entry in &plan.entries {
+            for action in &entry.actions {
+                if let Err(e) = Self::apply_action(action) {
+                    result.add_error(&entry.name, e.to_string());
+                    continue 'entries;
+                }
+            }
+
+            match &entry.error {
+                Some(error) => result.add_error(&entry.name, error.clone()),
+                None => result.add_success(&entry.name),
+            }
+        }
+
+        result
+    }
+
+    fn enabled_destinations(config: &Config) -> Vec<(&String, &DestinationConfig)> {
+        let mut destinations: Vec<_> = config
+            .destinations
+            .iter()
+            .filter(|(_, dest_config)| dest_config.enabled)
+            .collect();
+        destinations.sort_by_key(|(name, _)| *name);
+        destinations
+    }
+
+    /// Groups destinations that resolve to the same directory, so each
+    /// directory is planned once no matter how many tools read it.
+    fn group_by_path<'a>(
+        destinations: impl Iterator<Item = (&'a String, &'a PathBuf, LinkMode)>,
+    ) -> Vec<PathGroup<'a>> {
+        let mut groups: Vec<PathGroup<'a>> = Vec::new();
+
+        for (name, path, link_mode) in destinations {
+            let resolved = Self::resolved_path(path);
+            match groups.iter_mut().find(|group| group.resolved == resolved) {
+                Some(group) => group.members.push((name, link_mode)),
+                None => groups.push(PathGroup {
+                    path,
+                    resolved,
+                    members: vec![(name, link_mode)],
+                }),
+            }
+        }
+
+        groups
+    }
+
+    fn plan_group(group: &PathGroup, suffix: &str, source: &Path, kind: SourceKind) -> PlanEntry {
+        let names: Vec<&str> = group
+            .members
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect();
+        let label = format!("{}{}", names.join(", "), suffix);
+        let (tool, link_mode) = group.members[0];
+
+        if group
+            .members
+            .iter()
+            .any(|(_, other_mode)| *other_mode != link_mode)
+        {
+            let mut entry = PlanEntry::new(&label, group.path);
+            entry.error = Some(format!(
+                "{} share {} but use different link modes. Give them the same link_mode",
+                names.join(", "),
+                group.path.display()
+            ));
+            return entry;
+        }
+
+        Self::plan_destination(&label, tool, source, group.path, link_mode, kind)
+    }
+
+    /// Normalizes a destination path for comparison by resolving symlinks in
+    /// its parent directories. The last component is kept as-is, since it is
+    /// usually the link CapSync manages.
+    fn resolved_path(path: &Path) -> PathBuf {
+        match (path.parent(), path.file_name()) {
+            (Some(parent), Some(name)) => parent
+                .canonicalize()
+                .map(|parent| parent.join(name))
+                .unwrap_or_else(|_| path.to_path_buf()),
+            _ => path.to_path_buf(),
+        }
+    }
+
+    /// Other enabled tools whose skills or commands directory is the same as
+    /// one of `name`'s, and which would lose their links if `name` were removed.
+    pub fn tools_sharing_paths(name: &str, config: &Config) -> Vec<String> {
+        let Some(dest_config) = config.destinations.get(name) else {
+            return Vec::new();
+        };
+
+        let paths: Vec<PathBuf> = std::iter::once(&dest_config.skills_path)
+            .chain(dest_config.commands_path.as_ref())
+            .map(|path| Self::resolved_path(path))
+            .collect();
+
+        Self::enabled_destinations(config)
+            .into_iter()
+            .filter(|(other, _)| other.as_str() != name)
+            .filter(|(_, other_config)| {
+                std::iter::once(&other_config.skills_path)
+                    .chain(other_config.commands_path.as_ref())
+                    .any(|path| paths.contains(&Self::resolved_path(path)))
+            })
+            .map(|(other, _)| other.clone())
+            .collect()
+    }
+
+    fn plan_destination(
+        name: &str,
+        tool: &str,
+        source: &Path,
+        dest: &Path,
+        link_mode: LinkMode,
+        kind: SourceKind,
+    ) -> PlanEntry {
+        let mut entry = PlanEntry::new(name, dest);
+
+        let planned = match link_mode {
+            LinkMode::Symlink => Self::plan_symlink(tool, source, dest, &mut entry.actions),
+            LinkMode::PerSkill => Self::plan_per_skill(source, dest, kind, &mut entry.actions),
+            LinkMode::Copy | LinkMode::Hardlink => {
+                Self::plan_mirror(source, dest, kind, link_mode, &mut entry.actions)
+            }
+        };
+
+        if let Err(e) = planned {
+            entry.error = Some(e.to_string());
+        }
+
+        entry
+    }
+
+    fn plan_symlink(
+        tool: &str,
+        source: &Path,
+        dest: &Path,
+        actions: &mut Vec<PlannedAction>,
+    ) -> Result<()> {
+        if let Some(parent) = dest.parent() {
+            if !parent.exists() {
+                actions.push(PlannedAction::CreateDirectory {
+                    path: parent.to_path_buf(),
+                });
+            }
+        }
+
+        if dest.is_symlink() {
+            let old_target = fs::read_link(dest).unwrap_or_default();
+            if old_target == source {
+                actions.push(PlannedAction::Skip {
+                    path: dest.to_path_buf(),
+                    reason: "already linked".to_string(),
+                });
+            } else {
+                actions.push(PlannedAction::ReplaceLink {
+                    dest: dest.to_path_buf(),
+                    old_target,
+                    source: source.to_path_buf(),
+                });
+            }
+            return Ok(());
+        }
+
+        if dest.is_dir() && Self::is_empty_dir(dest)? {
+            actions.push(PlannedAction::RemoveEmptyDirectory {
+                path: dest.to_path_buf(),
+            });
+        } else if dest.exists() {
+            return Err(CapsyncError::DestinationConflict {
+                tool: tool.to_string(),
+                path: dest.to_path_buf(),
+            }
+            .into());
+        }
+
+        actions.push(PlannedAction::CreateLink {
+            source: source.to_path_buf(),
+            dest: dest.to_path_buf(),
+        });
+
+        Ok(())
+    }
+
+    /// Plans the directory that per-skill and mirror modes populate. Returns
+    /// true when the destination will start out empty.
+    fn plan_destination_directory(dest: &Path, actions: &mut Vec<PlannedAction>) -> Result<bool> {
+        if dest.is_symlink() {
+            // A whole-directory link left over from symlink mode.
+            actions.push(PlannedAction::RemoveLink {
+                path: dest.to_path_buf(),
+            });
+        } else if dest.exists() && !dest.is_dir() {
             return Err(anyhow!(
-                "Commands source directory does not exist: {}",
-                commands_source.display()
+                "Destination at {} exists but is not a directory",
+                dest.display()
             ));
+        } else if dest.is_dir() {
+            return Ok(false);
         }
 
-        for (name, dest_config) in &config.destinations {
-            if dest_config.enabled {
-                if let Some(commands_path) = &dest_config.commands_path {
-                    match Self::sync_destination(commands_source, commands_path) {
-                        Ok(()) => result.add_success(&format!("{name} (commands)")),
-                        Err(e) => result.add_error(&format!("{name} (commands)"), e.to_string()),
+        actions.push(PlannedAction::CreateDirectory {
+            path: dest.to_path_buf(),
+        });
+        Ok(true)
+    }
+
+    fn plan_per_skill(
+        source: &Path,
+        dest: &Path,
+        kind: SourceKind,
+        actions: &mut Vec<PlannedAction>,
+    ) -> Result<()> {
+        let fresh = Self::plan_destination_directory(dest, actions)?;
+        let entries = Self::source_entries(source, kind)?;
+        let mut conflicts = Vec::new();
+
+        for name in &entries {
+            let entry_source = source.join(name);
+            let entry_dest = dest.join(name);
+
+            if !fresh && entry_dest.is_symlink() {
+                if !Self::is_managed_link(&entry_dest, source) {
+                    conflicts.push(name.to_string_lossy().into_owned());
+                    continue;
+                }
+
+                let old_target = fs::read_link(&entry_dest).unwrap_or_default();
+                if old_target != entry_source {
+                    actions.push(PlannedAction::ReplaceLink {
+                        dest: entry_dest,
+                        old_target,
+                        source: entry_source,
+                    });
+                }
+            } else if !fresh && entry_dest.exists() {
+                conflicts.push(name.to_string_lossy().into_owned());
+            } else {
+                actions.push(PlannedAction::CreateLink {
+                    source: entry_source,
+                    dest: entry_dest,
+                });
+            }
+        }
+
+        if !fresh {
+            for stale in Self::stale_links(source, dest, &entries)? {
+                actions.push(PlannedAction::RemoveLink {
+                    path: dest.join(stale),
+                });
+            }
+        }
+
+        if !conflicts.is_empty() {
+            return Err(anyhow!(
+                "Not linked because an entry not managed by CapSync already exists: {}",
+                conflicts.join(", ")
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn plan_mirror(
+        source: &Path,
+        dest: &Path,
+        kind: SourceKind,
+        link_mode: LinkMode,
+        actions: &mut Vec<PlannedAction>,
+    ) -> Result<()> {
+        let fresh = Self::plan_destination_directory(dest, actions)?;
+        let previous = if fresh {
+            BTreeSet::new()
+        } else {
+            Self::read_manifest(dest)?
+        };
+        let conflicts = if fresh {
+            Vec::new()
+        } else {
+            Self::mirror_conflicts(source, dest, kind, &previous)?
+        };
+        let mut written = BTreeSet::new();
+
+        for entry in Self::source_entries(source, kind)? {
+            let name = entry.to_string_lossy().into_owned();
+            if conflicts.contains(&name) {
+                continue;
+            }
+
+            // A per-skill link left behind would make us write into the source.
+            let entry_dest = dest.join(&entry);
+            let replace_link = !fresh && Self::is_managed_link(&entry_dest, source);
+            if replace_link {
+                actions.push(PlannedAction::RemoveLink { path: entry_dest });
+            }
+
+            for relative in Self::mirror_files(source, Path::new(&entry))? {
+                let file_source = source.join(&relative);
+                let file_dest = dest.join(&relative);
+
+                if fresh
+                    || replace_link
+                    || !Self::file_matches(&file_source, &file_dest, link_mode)?
+                {
+                    actions.push(if link_mode == LinkMode::Hardlink {
+                        PlannedAction::HardlinkFile {
+                            source: file_source,
+                            dest: file_dest,
+                        }
+                    } else {
+                        PlannedAction::CopyFile {
+                            source: file_source,
+                            dest: file_dest,
+                        }
+                    });
+                }
+
+                written.insert(relative);
+            }
+        }
+
+        for relative in previous.difference(&written) {
+            let top_level = relative
+                .components()
+                .next()
+                .map(|component| component.as_os_str().to_string_lossy().into_owned());
+            if top_level.is_some_and(|name| conflicts.contains(&name)) {
+                continue;
+            }
+            actions.push(PlannedAction::RemoveCopiedFile {
+                root: dest.to_path_buf(),
+                relative: relative.clone(),
+            });
+        }
+
+        if !actions.is_empty() || written != previous {
+            actions.push(PlannedAction::WriteManifest {
+                root: dest.to_path_buf(),
+                files: written,
+            });
+        }
+
+        if !conflicts.is_empty() {
+            return Err(anyhow!(
+                "Not copied because an entry not managed by CapSync already exists: {}",
+                conflicts.join(", ")
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn apply_action(action: &PlannedAction) -> Result<()> {
+        match action {
+            PlannedAction::CreateDirectory { path } => fs::create_dir_all(path)
+                .with_context(|| format!("Failed to create directory {}", path.display())),
+            PlannedAction::CreateLink { source, dest } => Self::create_symlink(source, dest),
+            PlannedAction::ReplaceLink { dest, source, .. } => {
+                fs::remove_file(dest)
+                    .with_context(|| format!("Failed to remove symlink at {}", dest.display()))?;
+                Self::create_symlink(source, dest)
+            }
+            PlannedAction::RemoveLink { path } => fs::remove_file(path)
+                .with_context(|| format!("Failed to remove symlink at {}", path.display())),
+            PlannedAction::RemoveEmptyDirectory { path } => fs::remove_dir(path)
+                .with_context(|| format!("Failed to remove directory {}", path.display())),
+            PlannedAction::DeleteFile { path } => fs::remove_file(path)
+                .with_context(|| format!("Failed to remove {}", path.display())),
+            PlannedAction::DeleteDirectory { path } => fs::remove_dir_all(path)
+                .with_context(|| format!("Failed to remove directory {}", path.display())),
+            PlannedAction::CopyFile { source, dest } => {
+                Self::mirror_file(source, dest, LinkMode::Copy)
+            }
+            PlannedAction::HardlinkFile { source, dest } => {
+                Self::mirror_file(source, dest, LinkMode::Hardlink)
+            }
+            PlannedAction::RemoveCopiedFile { root, relative } => {
+                Self::remove_mirrored_file(root, relative)
+            }
+            PlannedAction::WriteManifest { root, files } => Self::write_manifest(root, files),
+            PlannedAction::Move { from, to } => fs::rename(from, to)
+                .with_context(|| format!("Failed to move {} to {}", from.display(), to.display())),
+            PlannedAction::Skip { .. } => Ok(()),
+        }
+    }
+
+    /// Compares a copy or hardlink destination against the source tree.
+    pub fn mirror_status(
+        source: &Path,
+        dest: &Path,
+        kind: SourceKind,
+        link_mode: LinkMode,
+    ) -> Result<MirrorStatus> {
+        let mut status = MirrorStatus::default();
+        let previous = Self::read_manifest(dest)?;
+        status.conflicts = Self::mirror_conflicts(source, dest, kind, &previous)?;
+
+        let mut current = BTreeSet::new();
+        if source.is_dir() {
+            for entry in Self::source_entries(source, kind)? {
+                if status
+                    .conflicts
+                    .contains(&entry.to_string_lossy().into_owned())
+                {
+                    continue;
+                }
+
+                for relative in Self::mirror_files(source, Path::new(&entry))? {
+                    let file_dest = dest.join(&relative);
+                    if !file_dest.exists() {
+                        status.missing.push(relative.clone());
+                    } else if !Self::file_matches(&source.join(&relative), &file_dest, link_mode)? {
+                        status.changed.push(relative.clone());
                     }
+                    current.insert(relative);
                 }
             }
         }
 
-        Ok(result)
+        status.stale = previous
+            .difference(&current)
+            .filter(|relative| dest.join(relative).exists())
+            .cloned()
+            .collect();
+
+        Ok(status)
     }
 
-    pub fn sync_all(config: &Config) -> Result<SyncResult> {
-        let mut result = SyncResult::new();
+    /// Top-level source entries whose destination counterpart CapSync did not create.
+    fn mirror_conflicts(
+        source: &Path,
+        dest: &Path,
+        kind: SourceKind,
+        manifest: &BTreeSet<PathBuf>,
+    ) -> Result<Vec<String>> {
+        let mut conflicts = Vec::new();
 
-        let skills_result = Self::sync_skills(config)?;
-        result.merge(skills_result);
+        if !source.is_dir() || !dest.is_dir() {
+            return Ok(conflicts);
+        }
 
-        if config.has_commands() {
-            let commands_result = Self::sync_commands(config)?;
-            result.merge(commands_result);
+        for entry in Self::source_entries(source, kind)? {
+            let entry_dest = dest.join(&entry);
+            let exists = entry_dest.exists() || entry_dest.is_symlink();
+            let managed = Self::is_managed_link(&entry_dest, source)
+                || manifest
+                    .iter()
+                    .any(|relative| relative.starts_with(Path::new(&entry)));
+
+            if exists && !managed {
+                conflicts.push(entry.to_string_lossy().into_owned());
+            }
         }
 
-        Ok(result)
+        Ok(conflicts)
     }
 
-    fn sync_destination(source: &Path, dest: &Path) -> Result<()> {
+    /// Relative paths of every file below `source/relative`, skipping `.git`.
+    fn mirror_files(source: &Path, relative: &Path) -> Result<Vec<PathBuf>> {
+        let path = source.join(relative);
+
+        if !path.is_dir() {
+            return Ok(vec![relative.to_path_buf()]);
+        }
+
+        let mut files = Vec::new();
+        for entry in fs::read_dir(&path)
+            .with_context(|| format!("Failed to read directory {}", path.display()))?
+        {
+            let entry = entry?;
+            if entry.file_name() == ".git" {
+                continue;
+            }
+            files.extend(Self::mirror_files(
+                source,
+                &relative.join(entry.file_name()),
+            )?);
+        }
+
+        files.sort();
+        Ok(files)
+    }
+
+    fn file_matches(source: &Path, dest: &Path, link_mode: LinkMode) -> Result<bool> {
+        if dest.is_symlink() || !dest.is_file() {
+            return Ok(false);
+        }
+
+        let same_file = Self::is_same_file(source, dest)?;
+        if link_mode == LinkMode::Hardlink || same_file {
+            // In copy mode a leftover hardlink would share edits with the source.
+            return Ok(link_mode == LinkMode::Hardlink && same_file);
+        }
+
+        let source_metadata = fs::metadata(source)
+            .with_context(|| format!("Failed to read metadata for {}", source.display()))?;
+        let dest_metadata = fs::metadata(dest)
+            .with_context(|| format!("Failed to read metadata for {}", dest.display()))?;
+        if source_metadata.len() != dest_metadata.len() {
+            return Ok(false);
+        }
+
+        let source_content =
+            fs::read(source).with_context(|| format!("Failed to read {}", source.display()))?;
+        let dest_content =
+            fs::read(dest).with_context(|| format!("Failed to read {}", dest.display()))?;
+
+        Ok(source_content == dest_content)
+    }
+
+    #[cfg(unix)]
+    fn is_same_file(source: &Path, dest: &Path) -> Result<bool> {
+        use std::os::unix::fs::MetadataExt;
+
+        let source_metadata = fs::metadata(source)
+            .with_context(|| format!("Failed to read metadata for {}", source.display()))?;
+        let dest_metadata = fs::metadata(dest)
+            .with_context(|| format!("Failed to read metadata for {}", dest.display()))?;
+
+        Ok(source_metadata.dev() == dest_metadata.dev()
+            && source_metadata.ino() == dest_metadata.ino())
+    }
+
+    #[cfg(not(unix))]
+    fn is_same_file(_source: &Path, _dest: &Path) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn mirror_file(source: &Path, dest: &Path, link_mode: LinkMode) -> Result<()> {
         if let Some(parent) = dest.parent() {
-            if !parent.exists() {
-                fs::create_dir_all(parent)?;
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+        }
+
+        if dest.is_symlink() || dest.is_file() {
+            fs::remove_file(dest)
+                .with_context(|| format!("Failed to remove {}", dest.display()))?;
+        } else if dest.is_dir() {
+            return Err(anyhow!(
+                "Cannot replace directory {} with a file",
+                dest.display()
+            ));
+        }
+
+        if link_mode == LinkMode::Hardlink {
+            fs::hard_link(source, dest).with_context(|| {
+                format!(
+                    "Failed to hard-link {} to {} (source and destination must be on the same filesystem)",
+                    source.display(),
+                    dest.display()
+                )
+            })?;
+        } else {
+            fs::copy(source, dest).with_context(|| {
+                format!("Failed to copy {} to {}", source.display(), dest.display())
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// Removes a mirrored file and any directories it leaves empty.
+    fn remove_mirrored_file(dest: &Path, relative: &Path) -> Result<()> {
+        let path = dest.join(relative);
+        if path.is_symlink() || path.is_file() {
+            fs::remove_file(&path)
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+        }
+
+        let mut parent = relative.parent();
+        while let Some(directory) = parent {
+            if directory.as_os_str().is_empty() {
+                break;
+            }
+            // Stops at the first directory that still has other entries.
+            if fs::remove_dir(dest.join(directory)).is_err() {
+                break;
             }
+            parent = directory.parent();
         }
 
-        if dest.exists() || dest.is_symlink() {
-            if dest.is_symlink() {
-                fs::remove_file(dest)?;
-            } else if dest.is_dir() {
-                fs::remove_dir_all(dest)?;
+        Ok(())
+    }
+
+    /// Reads the files a mirror wrote, refusing entries that would reach
+    /// outside `dest` since removal deletes whatever the manifest lists.
+    fn read_manifest(dest: &Path) -> Result<BTreeSet<PathBuf>> {
+        let manifest_path = dest.join(MIRROR_MANIFEST);
+        if !manifest_path.exists() {
+            return Ok(BTreeSet::new());
+        }
+
+        let content = fs::read_to_string(&manifest_path)
+            .with_context(|| format!("Failed to read {}", manifest_path.display()))?;
+
+        content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                let relative = PathBuf::from(line);
+                let inside = relative
+                    .components()
+                    .all(|component| matches!(component, Component::Normal(_)));
+                if inside {
+                    Ok(relative)
+                } else {
+                    Err(anyhow!(
+                        "{} lists '{}', which is outside {}",
+                        manifest_path.display(),
+                        line,
+                        dest.display()
+                    ))
+                }
+            })
+            .collect()
+    }
+
+    fn write_manifest(dest: &Path, files: &BTreeSet<PathBuf>) -> Result<()> {
+        let manifest_path = dest.join(MIRROR_MANIFEST);
+        let content: String = files
+            .iter()
+            .map(|relative| {
+                let components: Vec<_> = relative
+                    .components()
+                    .map(|component| component.as_os_str().to_string_lossy())
+                    .collect();
+                format!("{}\n", components.join("/"))
+            })
+            .collect();
+
+        fs::write(&manifest_path, content)
+            .with_context(|| format!("Failed to write {}", manifest_path.display()))
+    }
+
+    /// Reports which source entries are linked into a per-skill destination.
+    pub fn per_skill_status(
+        source: &Path,
+        dest: &Path,
+        kind: SourceKind,
+    ) -> Result<PerSkillStatus> {
+        let mut status = PerSkillStatus::default();
+
+        let entries = if source.is_dir() {
+            Self::source_entries(source, kind)?
+        } else {
+            Vec::new()
+        };
+
+        for name in &entries {
+            let entry_dest = dest.join(name);
+            let label = name.to_string_lossy().into_owned();
+
+            if entry_dest.is_symlink() && Self::is_managed_link(&entry_dest, source) {
+                status.linked.push(label);
+            } else if entry_dest.is_symlink() || entry_dest.exists() {
+                status.conflicts.push(label);
             } else {
-                fs::remove_file(dest)?;
+                status.missing.push(label);
+            }
+        }
+
+        status.stale = Self::stale_links(source, dest, &entries)?
+            .iter()
+            .map(|name| name.to_string_lossy().into_owned())
+            .collect();
+
+        Ok(status)
+    }
+
+    fn source_entries(source: &Path, kind: SourceKind) -> Result<Vec<OsString>> {
+        let mut entries = Vec::new();
+
+        for entry in fs::read_dir(source)
+            .with_context(|| format!("Failed to read directory {}", source.display()))?
+        {
+            let entry = entry?;
+            let name = entry.file_name();
+
+            if name.to_string_lossy().starts_with('.') {
+                continue;
+            }
+
+            if kind == SourceKind::Skills && !entry.path().is_dir() {
+                continue;
+            }
+
+            entries.push(name);
+        }
+
+        entries.sort();
+        Ok(entries)
+    }
+
+    /// Links in `dest` that CapSync created for entries no longer in the source.
+    fn stale_links(source: &Path, dest: &Path, entries: &[OsString]) -> Result<Vec<OsString>> {
+        let mut stale = Vec::new();
+
+        if dest.is_symlink() || !dest.is_dir() {
+            return Ok(stale);
+        }
+
+        for entry in fs::read_dir(dest)
+            .with_context(|| format!("Failed to read directory {}", dest.display()))?
+        {
+            let entry = entry?;
+            let name = entry.file_name();
+
+            if !entries.contains(&name) && Self::is_managed_link(&entry.path(), source) {
+                stale.push(name);
             }
         }
 
-        Self::create_symlink(source, dest)?;
+        stale.sort();
+        Ok(stale)
+    }
+
+    /// A link is managed by CapSync when it points inside the configured source.
+    fn is_managed_link(path: &Path, source: &Path) -> bool {
+        path.is_symlink()
+            && fs::read_link(path)
+                .map(|target| target.starts_with(source))
+                .unwrap_or(false)
+    }
+
+    pub fn remove(
+        name: &str,
+        config: &Config,
+        reporter: &dyn Reporter,
+    ) -> Result<(), CapsyncError> {
+        let plan = Self::plan_remove(name, config)?;
+        let result = Self::apply(&plan);
+        Self::report_removal(&plan, &result, reporter);
+
+        if result.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(CapsyncError::SyncFailed {
+                failures: result.errors,
+            })
+        }
+    }
+
+    pub fn remove_all(config: &Config, reporter: &dyn Reporter) -> Result<(), CapsyncError> {
+        let plan = Self::plan_remove_all(config);
+        let result = Self::apply(&plan);
+        Self::report_removal(&plan, &result, reporter);
+
+        for (name, error) in &result.errors {
+            reporter.warn(&format!("Failed to remove {name}: {error}"));
+        }
 
         Ok(())
     }
 
-    pub fn remove(name: &str, config: &Config) -> Result<()> {
-        if let Some(dest_config) = config.destinations.get(name) {
+    pub fn plan_remove(name: &str, config: &Config) -> Result<SyncPlan, CapsyncError> {
+        let dest_config = config
+            .destinations
+            .get(name)
+            .ok_or_else(|| CapsyncError::UnknownDestination(name.to_string()))?;
+
+        let mut plan = SyncPlan::new();
+        plan.entries.push(Self::plan_removal(
+            name,
+            Some(&config.skills_source),
+            &dest_config.skills_path,
+            dest_config.link_mode,
+        ));
+
+        if let Some(commands_dest) = &dest_config.commands_path {
+            if commands_dest.is_symlink() || commands_dest.exists() {
+                plan.entries.push(Self::plan_removal(
+                    &format!("{} (commands)", name),
+                    config.commands_source.as_deref(),
+                    commands_dest,
+                    dest_config.link_mode,
+                ));
+            }
+        }
+
+        Ok(plan)
+    }
+
+    pub fn plan_remove_all(config: &Config) -> SyncPlan {
+        let mut plan = SyncPlan::new();
+
+        let mut destinations: Vec<_> = config.destinations.iter().collect();
+        destinations.sort_by_key(|(name, _)| *name);
+
+        for (name, dest_config) in destinations {
             let dest = &dest_config.skills_path;
-            Self::remove_symlink(dest, name)?;
+            if dest.is_symlink() || dest.exists() {
+                plan.entries.push(Self::plan_removal(
+                    name,
+                    Some(&config.skills_source),
+                    dest,
+                    dest_config.link_mode,
+                ));
+            }
 
             if let Some(commands_dest) = &dest_config.commands_path {
                 if commands_dest.is_symlink() || commands_dest.exists() {
-                    Self::remove_symlink(commands_dest, &format!("{} (commands)", name))?;
+                    plan.entries.push(Self::plan_removal(
+                        &format!("{} (commands)", name),
+                        config.commands_source.as_deref(),
+                        commands_dest,
+                        dest_config.link_mode,
+                    ));
                 }
             }
+        }
+
+        plan
+    }
+
+    fn plan_removal(
+        name: &str,
+        source: Option<&Path>,
+        dest: &Path,
+        link_mode: LinkMode,
+    ) -> PlanEntry {
+        let mut entry = PlanEntry::new(name, dest);
+
+        let planned = match (link_mode, source) {
+            (LinkMode::Symlink, _) => Self::plan_remove_symlink(dest, &mut entry.actions),
+            (LinkMode::PerSkill, Some(source)) => {
+                Self::plan_remove_entry_links(source, dest, &mut entry.actions)
+            }
+            (LinkMode::PerSkill, None) => Err(anyhow!(
+                "Cannot tell which links at {} were created by CapSync without a commands_source",
+                dest.display()
+            )),
+            (LinkMode::Copy | LinkMode::Hardlink, _) => {
+                Self::plan_remove_mirror(dest, &mut entry.actions)
+            }
+        };
 
-            Ok(())
-        } else {
-            Err(anyhow!("Unknown destination: {}", name))
+        if let Err(e) = planned {
+            entry.error = Some(e.to_string());
         }
+
+        entry
     }
 
-    fn remove_symlink(dest: &Path, name: &str) -> Result<()> {
+    fn plan_remove_symlink(dest: &Path, actions: &mut Vec<PlannedAction>) -> Result<()> {
         if dest.is_symlink() {
-            fs::remove_file(dest)
-                .with_context(|| format!("Failed to remove symlink at {}", dest.display()))?;
-            println!("Removed {} symlink from {}", name, dest.display());
+            actions.push(PlannedAction::RemoveLink {
+                path: dest.to_path_buf(),
+            });
             Ok(())
         } else if dest.exists() {
             Err(anyhow!(
@@ -122,37 +1209,323 @@ impl SyncManager {
                 dest.display()
             ))
         } else {
-            println!(
-                "No {} symlink found at {} (already removed?)",
-                name,
-                dest.display()
-            );
             Ok(())
         }
     }
 
-    pub fn remove_all(config: &Config) -> Result<()> {
-        for (name, dest_config) in &config.destinations {
-            let dest = &dest_config.skills_path;
-            if dest.is_symlink() || dest.exists() {
-                match Self::remove_symlink(dest, name) {
-                    Ok(()) => {}
-                    Err(e) => eprintln!("Failed to remove symlink from {}: {e}", dest.display()),
-                }
+    /// Plans removal of only the per-entry links CapSync created, leaving the
+    /// destination directory and anything the tool put there in place.
+    fn plan_remove_entry_links(
+        source: &Path,
+        dest: &Path,
+        actions: &mut Vec<PlannedAction>,
+    ) -> Result<()> {
+        if dest.is_symlink() {
+            return Self::plan_remove_symlink(dest, actions);
+        }
+
+        if !dest.is_dir() {
+            return Ok(());
+        }
+
+        let mut links = Vec::new();
+        for entry in fs::read_dir(dest)
+            .with_context(|| format!("Failed to read directory {}", dest.display()))?
+        {
+            let path = entry?.path();
+            if Self::is_managed_link(&path, source) {
+                links.push(path);
             }
+        }
 
-            if let Some(commands_dest) = &dest_config.commands_path {
-                if commands_dest.is_symlink() || commands_dest.exists() {
-                    match Self::remove_symlink(commands_dest, &format!("{} (commands)", name)) {
-                        Ok(()) => {}
-                        Err(e) => eprintln!("Failed to remove commands symlink: {e}"),
-                    }
+        links.sort();
+        actions.extend(
+            links
+                .into_iter()
+                .map(|path| PlannedAction::RemoveLink { path }),
+        );
+        Ok(())
+    }
+
+    /// Plans removal of the per-skill links to `slug` in every enabled
+    /// destination, for a skill that is leaving `skills_source`.
+    pub fn plan_unlink_skill(slug: &str, config: &Config) -> SyncPlan {
+        let mut plan = SyncPlan::new();
+        let destinations = Self::enabled_destinations(config)
+            .into_iter()
+            .filter(|(_, dest_config)| dest_config.link_mode == LinkMode::PerSkill)
+            .map(|(name, dest_config)| (name, &dest_config.skills_path, dest_config.link_mode));
+
+        for group in Self::group_by_path(destinations) {
+            let link = group.path.join(slug);
+            if !Self::is_managed_link(&link, &config.skills_source) {
+                continue;
+            }
+
+            let names: Vec<&str> = group
+                .members
+                .iter()
+                .map(|(name, _)| name.as_str())
+                .collect();
+            let mut entry = PlanEntry::new(&names.join(", "), group.path);
+            entry.actions.push(PlannedAction::RemoveLink { path: link });
+            plan.entries.push(entry);
+        }
+
+        plan
+    }
+
+    /// Plans removal of the files listed in the mirror manifest, leaving anything else.
+    fn plan_remove_mirror(dest: &Path, actions: &mut Vec<PlannedAction>) -> Result<()> {
+        if dest.is_symlink() {
+            return Self::plan_remove_symlink(dest, actions);
+        }
+
+        let manifest_path = dest.join(MIRROR_MANIFEST);
+        if !manifest_path.exists() {
+            return Ok(());
+        }
+
+        for relative in Self::read_manifest(dest)? {
+            actions.push(PlannedAction::RemoveCopiedFile {
+                root: dest.to_path_buf(),
+                relative,
+            });
+        }
+        actions.push(PlannedAction::DeleteFile {
+            path: manifest_path,
+        });
+
+        Ok(())
+    }
+
+    /// Plans moving a tool's existing, non-symlink destinations out of the way
+    /// so the next sync can link them.
+    pub fn plan_adopt(
+        name: &str,
+        config: &Config,
+        strategy: AdoptStrategy,
+    ) -> Result<SyncPlan, CapsyncError> {
+        let dest_config = config
+            .destinations
+            .get(name)
+            .ok_or_else(|| CapsyncError::UnknownDestination(name.to_string()))?;
+
+        let mut plan = SyncPlan::new();
+        plan.entries.push(Self::plan_adopt_destination(
+            name,
+            name,
+            Some(&config.skills_source),
+            &dest_config.skills_path,
+            strategy,
+        ));
+
+        if let Some(commands_dest) = &dest_config.commands_path {
+            let commands_source = config
+                .commands_source
+                .as_deref()
+                .filter(|source| !source.as_os_str().is_empty());
+            plan.entries.push(Self::plan_adopt_destination(
+                &format!("{} (commands)", name),
+                name,
+                commands_source,
+                commands_dest,
+                strategy,
+            ));
+        }
+
+        Ok(plan)
+    }
+
+    fn plan_adopt_destination(
+        name: &str,
+        tool: &str,
+        source: Option<&Path>,
+        dest: &Path,
+        strategy: AdoptStrategy,
+    ) -> PlanEntry {
+        let mut entry = PlanEntry::new(name, dest);
+
+        let planned = if dest.is_symlink() || !dest.exists() {
+            Ok(())
+        } else {
+            match (strategy, source) {
+                (AdoptStrategy::Backup, _) => Self::plan_backup(dest, &mut entry.actions),
+                (AdoptStrategy::MoveToSource, Some(source)) => {
+                    Self::plan_move_to_source(tool, source, dest, &mut entry.actions)
                 }
+                (AdoptStrategy::MoveToSource, None) => Err(anyhow!(
+                    "No commands_source configured to move {} into. Use --backup instead.",
+                    dest.display()
+                )),
+            }
+        };
+
+        if let Err(e) = planned {
+            entry.error = Some(e.to_string());
+        }
+
+        entry
+    }
+
+    fn plan_backup(dest: &Path, actions: &mut Vec<PlannedAction>) -> Result<()> {
+        let file_name = dest
+            .file_name()
+            .ok_or_else(|| anyhow!("Cannot back up {}", dest.display()))?;
+        let timestamp = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .context("Failed to get timestamp")?
+            .as_secs();
+
+        actions.push(PlannedAction::Move {
+            from: dest.to_path_buf(),
+            to: dest.with_file_name(format!(
+                "{}.backup.{}",
+                file_name.to_string_lossy(),
+                timestamp
+            )),
+        });
+        Ok(())
+    }
+
+    /// Plans moving every entry of `dest` into `source`. Links and copies
+    /// CapSync made earlier are removed instead, since the source already has them.
+    fn plan_move_to_source(
+        tool: &str,
+        source: &Path,
+        dest: &Path,
+        actions: &mut Vec<PlannedAction>,
+    ) -> Result<()> {
+        if !dest.is_dir() {
+            return Err(anyhow!(
+                "Destination at {} is not a directory. Use --backup to move it aside.",
+                dest.display()
+            ));
+        }
+
+        if !source.is_dir() {
+            return Err(anyhow!(
+                "Source directory does not exist: {}",
+                source.display()
+            ));
+        }
+
+        let manifest = Self::read_manifest(dest)?;
+        for relative in &manifest {
+            actions.push(PlannedAction::RemoveCopiedFile {
+                root: dest.to_path_buf(),
+                relative: relative.clone(),
+            });
+        }
+        if dest.join(MIRROR_MANIFEST).exists() {
+            actions.push(PlannedAction::DeleteFile {
+                path: dest.join(MIRROR_MANIFEST),
+            });
+        }
+
+        let mut names: Vec<_> = fs::read_dir(dest)
+            .with_context(|| format!("Failed to read directory {}", dest.display()))?
+            .map(|entry| entry.map(|entry| entry.file_name()))
+            .collect::<std::io::Result<_>>()?;
+        names.sort();
+
+        let mut claimed = BTreeSet::new();
+        for name in names {
+            let path = dest.join(&name);
+
+            if name == MIRROR_MANIFEST
+                || manifest
+                    .iter()
+                    .any(|relative| relative.starts_with(Path::new(&name)))
+            {
+                continue;
+            }
+
+            if Self::is_managed_link(&path, source) {
+                actions.push(PlannedAction::RemoveLink { path });
+                continue;
             }
+
+            let target = Self::unclaimed_name(source, &name, path.is_dir(), tool, &mut claimed);
+            actions.push(PlannedAction::Move {
+                from: path,
+                to: source.join(target),
+            });
         }
+
+        actions.push(PlannedAction::RemoveEmptyDirectory {
+            path: dest.to_path_buf(),
+        });
         Ok(())
     }
 
+    /// Picks a name in `source` that is free, appending the tool name (and a
+    /// counter if needed) before any file extension when `name` is taken.
+    fn unclaimed_name(
+        source: &Path,
+        name: &OsString,
+        is_dir: bool,
+        tool: &str,
+        claimed: &mut BTreeSet<OsString>,
+    ) -> OsString {
+        let taken = |candidate: &OsString, claimed: &BTreeSet<OsString>| {
+            let path = source.join(candidate);
+            path.exists() || path.is_symlink() || claimed.contains(candidate)
+        };
+
+        let mut candidate = name.clone();
+        let original = Path::new(name);
+        let (stem, extension) = match (original.file_stem(), original.extension()) {
+            (Some(stem), Some(extension)) if !is_dir => (
+                stem.to_string_lossy().into_owned(),
+                format!(".{}", extension.to_string_lossy()),
+            ),
+            _ => (name.to_string_lossy().into_owned(), String::new()),
+        };
+
+        let mut counter = 1;
+        while taken(&candidate, claimed) {
+            candidate = if counter == 1 {
+                OsString::from(format!("{stem}-{tool}{extension}"))
+            } else {
+                OsString::from(format!("{stem}-{tool}-{counter}{extension}"))
+            };
+            counter += 1;
+        }
+
+        claimed.insert(candidate.clone());
+        candidate
+    }
+
+    fn is_empty_dir(path: &Path) -> Result<bool> {
+        Ok(fs::read_dir(path)
+            .with_context(|| format!("Failed to read directory {}", path.display()))?
+            .next()
+            .is_none())
+    }
+
+    fn report_removal(plan: &SyncPlan, result: &SyncResult, reporter: &dyn Reporter) {
+        for entry in &plan.entries {
+            if !result.successful.contains(&entry.name) {
+                continue;
+            }
+
+            if entry.has_changes() {
+                reporter.info(&format!(
+                    "Removed {} links from {}",
+                    entry.name,
+                    entry.path.display()
+                ));
+            } else {
+                reporter.info(&format!(
+                    "No {} links found at {} (already removed?)",
+                    entry.name,
+                    entry.path.display()
+                ));
+            }
+        }
+    }
+
     fn create_symlink(source: &Path, dest: &Path) -> Result<()> {
         #[cfg(unix)]
         {
@@ -167,7 +1540,12 @@ impl SyncManager {
 
         #[cfg(windows)]
         {
-            std::os::windows::fs::symlink_dir(source, dest).with_context(|| {
+            let result = if source.is_dir() {
+                std::os::windows::fs::symlink_dir(source, dest)
+            } else {
+                std::os::windows::fs::symlink_file(source, dest)
+            };
+            result.with_context(|| {
                 format!(
                     "Failed to create symlink from {} to {}",
                     source.display(),
@@ -214,17 +1592,21 @@ impl SyncResult {
     }
 
     pub fn print(&self) {
+        self.report(&Terminal);
+    }
+
+    pub fn report(&self, reporter: &dyn Reporter) {
         if !self.successful.is_empty() {
-            println!("\nSynced successfully:");
+            reporter.info("\nSynced successfully:");
             for name in &self.successful {
-                println!("  {name}");
+                reporter.info(&format!("  {name}"));
             }
         }
 
         if !self.errors.is_empty() {
-            println!("\nErrors:");
+            reporter.info("\nErrors:");
             for (name, error) in &self.errors {
-                println!("  {name}: {error}");
+                reporter.info(&format!("  {name}: {error}"));
             }
         }
     }
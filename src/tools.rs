@@ -1,168 +1,288 @@
-use std::path::PathBuf;
+use crate::config::CustomToolConfig;
+use crate::paths;
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Component, Path, PathBuf};
 use std::sync::LazyLock;
 
-static ALL_TOOLS_VEC: LazyLock<Vec<Tool>> = LazyLock::new(|| {
-    vec![
-        Tool::new("adal", ".adal", ".adal/skills", None),
-        Tool::new("aider-desk", ".aider-desk", ".aider-desk/skills", None),
-        Tool::new("amp", ".config/agents", ".config/agents/skills", None),
-        Tool::new(
-            "antigravity",
-            ".gemini/antigravity",
-            ".gemini/antigravity/skills",
-            None,
-        ),
-        Tool::new(
-            "antigravity-cli",
-            ".gemini/antigravity-cli",
-            ".gemini/antigravity-cli/skills",
-            None,
-        ),
-        Tool::new(
-            "antigravity-ide",
-            ".gemini/antigravity-ide",
-            ".gemini/antigravity-ide/skills",
-            None,
-        ),
-        Tool::new("astrbot", ".astrbot", ".astrbot/data/skills", None),
-        Tool::new("autohand-code", ".autohand", ".autohand/skills", None),
-        Tool::new("augment", ".augment", ".augment/skills", None),
-        Tool::new("bob", ".bob", ".bob/skills", None),
-        Tool::new(
-            "claude",
-            ".claude",
-            ".claude/skills",
-            Some(".claude/commands"),
-        ),
-        Tool::new(
-            "claude-code",
-            ".claude",
-            ".claude/skills",
-            Some(".claude/commands"),
-        ),
-        Tool::new("cline", ".cline", ".cline/skills", None),
-        Tool::new(
-            "codearts-agent",
-            ".codeartsdoer",
-            ".codeartsdoer/skills",
-            None,
-        ),
-        Tool::new("codebuddy", ".codebuddy", ".codebuddy/skills", None),
-        Tool::new("codemaker", ".codemaker", ".codemaker/skills", None),
-        Tool::new("codestudio", ".codestudio", ".codestudio/skills", None),
-        Tool::new("codex", ".codex", ".codex/skills", Some(".codex/commands")),
-        Tool::new("command-code", ".commandcode", ".commandcode/skills", None),
-        Tool::new("continue", ".continue", ".continue/skills", None),
-        Tool::new("cortex", ".snowflake/cortex", ".cortex/skills", None),
-        Tool::new("crush", ".config/crush", ".config/crush/skills", None),
-        Tool::new("cursor", ".cursor", ".cursor/skills", None),
-        Tool::new(
-            "deepagents",
-            ".deepagents",
-            ".deepagents/agent/skills",
-            None,
-        ),
-        Tool::new("devin", ".config/devin", ".config/devin/skills", None),
-        Tool::new("dexto", ".dexto", ".agents/skills", None),
-        Tool::new("droid", ".factory", ".factory/skills", None),
-        Tool::new("firebender", ".firebender", ".firebender/skills", None),
-        Tool::new("forgecode", ".forge", ".forge/skills", None),
-        Tool::new("gemini-cli", ".gemini", ".gemini/skills", None),
-        Tool::new("github-copilot", ".copilot", ".copilot/skills", None),
-        Tool::new("goose", ".config/goose", ".config/goose/skills", None),
-        Tool::new("hermes-agent", ".hermes", ".hermes/skills", None),
-        Tool::new("iflow-cli", ".iflow", ".iflow/skills", None),
-        Tool::new("inference-sh", ".inferencesh", ".inferencesh/skills", None),
-        Tool::new("jazz", ".jazz", ".jazz/skills", None),
-        Tool::new("junie", ".junie", ".junie/skills", None),
-        Tool::new(
-            "kilo",
-            ".kilocode",
-            ".kilocode/skills",
-            Some(".kilocode/commands"),
-        ),
-        Tool::new("kimi-cli", ".config/agents", ".config/agents/skills", None),
-        Tool::new("kimi-code-cli", ".kimi-code", ".agents/skills", None),
-        Tool::new("kiro-cli", ".kiro", ".kiro/skills", None),
-        Tool::new("kode", ".kode", ".kode/skills", None),
-        Tool::new("lingma", ".lingma", ".lingma/skills", None),
-        Tool::new("loaf", ".loaf", ".agents/skills", None),
-        Tool::new("mcpjam", ".mcpjam", ".mcpjam/skills", None),
-        Tool::new("mistral-vibe", ".vibe", ".vibe/skills", None),
-        Tool::new("moltbot", ".moltbot", ".moltbot/skills", None),
-        Tool::new("moxby", ".moxby", ".moxby/skills", None),
-        Tool::new("mux", ".mux", ".mux/skills", None),
-        Tool::new("neovate", ".neovate", ".neovate/skills", None),
-        Tool::new("ona", ".ona", ".ona/skills", None),
-        Tool::new(
-            "opencode",
-            ".config/opencode",
-            ".config/opencode/skill",
-            Some(".config/opencode/commands"),
-        ),
-        Tool::new("openhands", ".openhands", ".openhands/skills", None),
-        Tool::new("openclaw", ".moltbot", ".moltbot/skills", None),
-        Tool::new("pi", ".pi/agent", ".pi/agent/skills", None),
-        Tool::new("pochi", ".pochi", ".pochi/skills", None),
-        Tool::new("qoder", ".qoder", ".qoder/skills", None),
-        Tool::new("qoder-cn", ".qoder-cn", ".qoder-cn/skills", None),
-        Tool::new("qwen-code", ".qwen", ".qwen/skills", None),
-        Tool::new("reasonix", ".reasonix", ".reasonix/skills", None),
-        Tool::new("replit", ".config/agents", ".config/agents/skills", None),
-        Tool::new("rovodev", ".rovodev", ".rovodev/skills", None),
-        Tool::new("roo", ".roo", ".roo/skills", None),
-        Tool::new("tabnine-cli", ".tabnine", ".tabnine/agent/skills", None),
-        Tool::new("terramind", ".terramind", ".terramind/skills", None),
-        Tool::new("tinycloud", ".tinycloud", ".tinycloud/skills", None),
-        Tool::new("trae", ".trae", ".trae/skills", None),
-        Tool::new("trae-cn", ".trae-cn", ".trae-cn/skills", None),
-        Tool::new("warp", ".warp", ".agents/skills", None),
-        Tool::new(
-            "windsurf",
-            ".codeium/windsurf",
-            ".codeium/windsurf/skills",
-            None,
-        ),
-        Tool::new("zed", ".config/zed", ".agents/skills", None),
-        Tool::new("zencoder", ".zencoder", ".zencoder/skills", None),
-        Tool::new("zenflow", ".zencoder", ".zencoder/skills", None),
-    ]
-});
+/// The built-in registry, shipped inside the binary. Adding a tool only
+/// means adding an entry to this file.
+const BUILTIN_REGISTRY: &str = include_str!("tools.toml");
 
-/// HashMap for O(1) tool lookups by name.
-/// NOTE: Depends on ALL_TOOLS_VEC being initialized first (Rust's LazyLock handles this).
-static TOOLS_BY_NAME: LazyLock<std::collections::HashMap<&'static str, Tool>> =
-    LazyLock::new(|| ALL_TOOLS_VEC.iter().map(|t| (t.name, t.clone())).collect());
+/// Built-in entries with paths relative to the home directory. They are
+/// resolved when looked up, so `--home` and `CAPSYNC_HOME` apply to them.
+static ALL_TOOLS_VEC: LazyLock<Vec<RegistryEntry>> = LazyLock::new(|| {
+    toml::from_str::<RegistryFile>(BUILTIN_REGISTRY)
+        .expect("built-in tool registry is valid TOML")
+        .tools
+});
 
 #[derive(Debug, Clone)]
 pub struct Tool {
-    pub name: &'static str,
+    pub name: String,
     pub config_path: PathBuf,
     pub skills_path: PathBuf,
     pub commands_path: Option<PathBuf>,
 }
 
 impl Tool {
-    fn new(
-        name: &'static str,
-        config_subpath: &str,
-        skills_subpath: &str,
-        commands_subpath: Option<&str>,
-    ) -> Self {
-        let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("~"));
+    fn from_entry(entry: &RegistryEntry, home: &Path) -> Self {
         Self {
-            name,
-            config_path: home.join(config_subpath),
-            skills_path: home.join(skills_subpath),
-            commands_path: commands_subpath.map(|path| home.join(path)),
+            name: entry.name.clone(),
+            config_path: home.join(&entry.detect_path),
+            skills_path: home.join(&entry.skills_path),
+            commands_path: entry.commands_path.as_ref().map(|path| home.join(path)),
         }
     }
 }
 
-pub fn all_tools() -> &'static Vec<Tool> {
-    &ALL_TOOLS_VEC
+/// A registry data file: the embedded `tools.toml` or a user-supplied one.
+#[derive(Debug, Deserialize)]
+struct RegistryFile {
+    #[serde(default)]
+    tools: Vec<RegistryEntry>,
 }
 
+/// One tool in a registry file. Paths are relative to the home directory.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RegistryEntry {
+    name: String,
+    detect_path: PathBuf,
+    skills_path: PathBuf,
+    #[serde(default)]
+    commands_path: Option<PathBuf>,
+}
+
+/// Every tool in [`ToolRegistry::configured`]: built-in tools, the user
+/// registry file, and the config's `[custom_tools]`.
+pub fn all_tools() -> Vec<Tool> {
+    ToolRegistry::configured().tools
+}
+
+/// Looks a tool up in [`ToolRegistry::configured`].
 pub fn get_tool(name: &str) -> Option<Tool> {
-    TOOLS_BY_NAME.get(name).cloned()
+    ToolRegistry::configured().get(name).cloned()
+}
+
+/// Built-in tools merged with the user registry file and the `[custom_tools]`
+/// defined in the config. Later sources replace tools with the same name.
+#[derive(Debug, Clone)]
+pub struct ToolRegistry {
+    home: PathBuf,
+    tools: Vec<Tool>,
+}
+
+impl ToolRegistry {
+    pub fn builtin() -> Self {
+        Self::builtin_in(&paths::home_dir())
+    }
+
+    /// The built-in tools with paths resolved against `home`.
+    pub fn builtin_in(home: &Path) -> Self {
+        Self {
+            home: home.to_path_buf(),
+            tools: ALL_TOOLS_VEC
+                .iter()
+                .map(|entry| Tool::from_entry(entry, home))
+                .collect(),
+        }
+    }
+
+    /// [`ToolRegistry::load`] with the `[custom_tools]` of the config, if there
+    /// is one. Falls back to the built-in tools when the registry file cannot
+    /// be read.
+    pub fn configured() -> Self {
+        let custom_tools = crate::config::load_config()
+            .map(|config| config.custom_tools)
+            .unwrap_or_default();
+        Self::load(&custom_tools).unwrap_or_else(|_| Self::builtin())
+    }
+
+    /// Built-in tools, then the user registry file if it exists, then `custom_tools`.
+    pub fn load(custom_tools: &BTreeMap<String, CustomToolConfig>) -> Result<Self> {
+        Self::load_in(&paths::home_dir(), &paths::registry_path(), custom_tools)
+    }
+
+    /// Like [`ToolRegistry::load`], with an explicit home and registry file.
+    pub fn load_in(
+        home: &Path,
+        registry_path: &Path,
+        custom_tools: &BTreeMap<String, CustomToolConfig>,
+    ) -> Result<Self> {
+        let mut registry = Self::builtin_in(home);
+
+        if registry_path.exists() {
+            registry.merge_file(registry_path)?;
+        }
+
+        registry.add_custom_tools(custom_tools);
+        Ok(registry)
+    }
+
+    pub fn home(&self) -> &Path {
+        &self.home
+    }
+
+    /// Adds or replaces tools with the entries of a registry file.
+    pub fn merge_file(&mut self, path: &Path) -> Result<()> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read tool registry {}", path.display()))?;
+        let file: RegistryFile = toml::from_str(&content)
+            .with_context(|| format!("Failed to parse tool registry {}", path.display()))?;
+
+        for entry in &file.tools {
+            let tool = Tool::from_entry(entry, &self.home);
+            self.insert(tool);
+        }
+
+        Ok(())
+    }
+
+    fn add_custom_tools(&mut self, custom_tools: &BTreeMap<String, CustomToolConfig>) {
+        for (name, custom) in custom_tools {
+            let home = &self.home;
+            let tool = Tool {
+                name: name.clone(),
+                config_path: paths::expand_tilde(&custom.detect_path, home),
+                skills_path: paths::expand_tilde(&custom.skills_path, home),
+                commands_path: custom
+                    .commands_path
+                    .as_deref()
+                    .map(|path| paths::expand_tilde(path, home)),
+            };
+            self.insert(tool);
+        }
+    }
+
+    fn insert(&mut self, tool: Tool) {
+        match self
+            .tools
+            .iter_mut()
+            .find(|existing| existing.name == tool.name)
+        {
+            Some(existing) => *existing = tool,
+            None => {
+                self.tools.push(tool);
+                self.tools.sort_by(|a, b| a.name.cmp(&b.name));
+            }
+        }
+    }
+
+    pub fn all(&self) -> &[Tool] {
+        &self.tools
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Tool> {
+        self.tools.iter().find(|tool| tool.name == name)
+    }
+
+    /// Other tools that read the same skills directory as `name`.
+    pub fn tools_sharing_skills_path(&self, name: &str) -> Vec<&str> {
+        let Some(tool) = self.get(name) else {
+            return Vec::new();
+        };
+
+        self.tools
+            .iter()
+            .filter(|other| other.name != tool.name && other.skills_path == tool.skills_path)
+            .map(|other| other.name.as_str())
+            .collect()
+    }
+}
+
+/// Problems found in a registry file by [`validate_registry`].
+#[derive(Debug, Default)]
+pub struct RegistryReport {
+    /// Entries CapSync cannot use as written.
+    pub errors: Vec<String>,
+    /// Tools that share a directory. Allowed, but worth a second look.
+    pub warnings: Vec<String>,
+}
+
+impl RegistryReport {
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// Checks a registry file for duplicate names, paths that are not relative to
+/// the home directory, and tools that share a directory.
+pub fn validate_registry(content: &str) -> Result<RegistryReport> {
+    let file: RegistryFile = toml::from_str(content).context("Failed to parse tool registry")?;
+    let mut report = RegistryReport::default();
+    let mut names: HashMap<&str, usize> = HashMap::new();
+    let mut skills_paths: BTreeMap<&Path, Vec<&str>> = BTreeMap::new();
+    let mut commands_paths: BTreeMap<&Path, Vec<&str>> = BTreeMap::new();
+
+    for entry in &file.tools {
+        let name = entry.name.as_str();
+        if name.trim().is_empty() {
+            report.errors.push("A tool has an empty name".to_string());
+        }
+
+        *names.entry(name).or_default() += 1;
+        if names[name] == 2 {
+            report
+                .errors
+                .push(format!("Duplicate tool name '{}'", name));
+        }
+
+        let mut paths = vec![
+            ("detect_path", entry.detect_path.as_path()),
+            ("skills_path", entry.skills_path.as_path()),
+        ];
+        if let Some(commands_path) = &entry.commands_path {
+            paths.push(("commands_path", commands_path.as_path()));
+        }
+
+        for (field, path) in paths {
+            if !is_home_relative(path) {
+                report.errors.push(format!(
+                    "{}.{} must be a relative path inside the home directory, got '{}'",
+                    name,
+                    field,
+                    path.display()
+                ));
+            }
+        }
+
+        skills_paths
+            .entry(entry.skills_path.as_path())
+            .or_default()
+            .push(name);
+        if let Some(commands_path) = &entry.commands_path {
+            commands_paths
+                .entry(commands_path.as_path())
+                .or_default()
+                .push(name);
+        }
+    }
+
+    for (field, shared) in [
+        ("skills_path", skills_paths),
+        ("commands_path", commands_paths),
+    ] {
+        for (path, tools) in shared {
+            if tools.len() > 1 {
+                report.warnings.push(format!(
+                    "{} share {} '{}'",
+                    tools.join(", "),
+                    field,
+                    path.display()
+                ));
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+fn is_home_relative(path: &Path) -> bool {
+    !path.as_os_str().is_empty()
+        && !path.to_string_lossy().starts_with('~')
+        && path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
 }
@@ -0,0 +1,234 @@
+use crate::error::CapsyncError;
+use anyhow::{Result, anyhow};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::io::{self, IsTerminal, Write};
+
+/// Asks the user to decide something. Library functions that may need an
+/// answer take a `&dyn Prompter`, so embedders can show their own dialogs.
+pub trait Prompter {
+    /// A yes/no question. Empty input picks `default`.
+    fn confirm(&self, question: &str, default: bool) -> Result<bool>;
+
+    /// Picks one of `choices` and returns its index. Empty input picks `default`.
+    fn choose(&self, question: &str, choices: &[&str], default: usize) -> Result<usize>;
+
+    /// A free-form answer, trimmed.
+    fn ask(&self, question: &str) -> Result<String>;
+}
+
+/// Receives the progress messages and warnings library functions produce.
+pub trait Reporter {
+    fn info(&self, message: &str);
+
+    fn warn(&self, message: &str);
+}
+
+/// Prompts on stdin and reports on stdout/stderr, as the `capsync` binary does.
+/// Prompting fails with [`CapsyncError::InputRequired`] instead of blocking
+/// when stdin is not a terminal.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Terminal;
+
+impl Terminal {
+    fn read_answer(question: &str, hint: &str) -> Result<String> {
+        if !io::stdin().is_terminal() {
+            return Err(CapsyncError::InputRequired {
+                question: question.to_string(),
+                flag: None,
+            }
+            .into());
+        }
+
+        if hint.is_empty() {
+            print!("{} ", question);
+        } else {
+            print!("{} [{}]: ", question, hint);
+        }
+        io::stdout().flush()?;
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        Ok(input.trim().to_string())
+    }
+}
+
+impl Prompter for Terminal {
+    fn confirm(&self, question: &str, default: bool) -> Result<bool> {
+        let hint = if default { "Y/n" } else { "y/N" };
+        loop {
+            let input = Self::read_answer(question, hint)?;
+            if let Some(answer) = parse_confirm(&input, default) {
+                return Ok(answer);
+            }
+            println!("Please enter y or n.");
+        }
+    }
+
+    fn choose(&self, question: &str, choices: &[&str], default: usize) -> Result<usize> {
+        let keys: Vec<String> = choices
+            .iter()
+            .enumerate()
+            .map(|(index, choice)| {
+                let key = choice.chars().next().unwrap_or('?');
+                if index == default {
+                    key.to_uppercase().to_string()
+                } else {
+                    key.to_lowercase().to_string()
+                }
+            })
+            .collect();
+        let hint = keys.join("/");
+
+        loop {
+            let input = Self::read_answer(question, &hint)?;
+            if let Some(index) = parse_choice(&input, choices, default) {
+                return Ok(index);
+            }
+            println!("Please enter {}.", keys.join(" or "));
+        }
+    }
+
+    fn ask(&self, question: &str) -> Result<String> {
+        Self::read_answer(question, "")
+    }
+}
+
+impl Reporter for Terminal {
+    fn info(&self, message: &str) {
+        println!("{}", message);
+    }
+
+    fn warn(&self, message: &str) {
+        eprintln!("Warning: {}", message);
+    }
+}
+
+/// A prompter for calls whose answers are decided up front. Every question
+/// fails with [`CapsyncError::InputRequired`], so a prompt that should not
+/// happen surfaces as an error instead of a guessed answer.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NonInteractive;
+
+impl NonInteractive {
+    fn refuse<T>(question: &str) -> Result<T> {
+        Err(CapsyncError::InputRequired {
+            question: question.to_string(),
+            flag: None,
+        }
+        .into())
+    }
+}
+
+impl Prompter for NonInteractive {
+    fn confirm(&self, question: &str, _default: bool) -> Result<bool> {
+        Self::refuse(question)
+    }
+
+    fn choose(&self, question: &str, _choices: &[&str], _default: usize) -> Result<usize> {
+        Self::refuse(question)
+    }
+
+    fn ask(&self, question: &str) -> Result<String> {
+        Self::refuse(question)
+    }
+}
+
+/// Answers prompts from a fixed script and records everything reported, for
+/// tests and non-interactive embedders. Running out of answers is an error.
+#[derive(Debug, Default)]
+pub struct Scripted {
+    answers: RefCell<VecDeque<String>>,
+    questions: RefCell<Vec<String>>,
+    messages: RefCell<Vec<String>>,
+    warnings: RefCell<Vec<String>>,
+}
+
+impl Scripted {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queues the raw answer to the next question, as a user would type it
+    /// (`"y"`, `"o"`, a path, or `""` for the default).
+    pub fn answer(self, answer: impl Into<String>) -> Self {
+        self.answers.borrow_mut().push_back(answer.into());
+        self
+    }
+
+    /// The questions asked so far, in order.
+    pub fn questions(&self) -> Vec<String> {
+        self.questions.borrow().clone()
+    }
+
+    pub fn messages(&self) -> Vec<String> {
+        self.messages.borrow().clone()
+    }
+
+    pub fn warnings(&self) -> Vec<String> {
+        self.warnings.borrow().clone()
+    }
+
+    fn next_answer(&self, question: &str) -> Result<String> {
+        self.questions.borrow_mut().push(question.to_string());
+        self.answers
+            .borrow_mut()
+            .pop_front()
+            .ok_or_else(|| anyhow!("No scripted answer for \"{}\"", question))
+    }
+}
+
+impl Prompter for Scripted {
+    fn confirm(&self, question: &str, default: bool) -> Result<bool> {
+        let input = self.next_answer(question)?;
+        parse_confirm(&input, default)
+            .ok_or_else(|| anyhow!("Scripted answer '{}' is not y or n", input))
+    }
+
+    fn choose(&self, question: &str, choices: &[&str], default: usize) -> Result<usize> {
+        let input = self.next_answer(question)?;
+        parse_choice(&input, choices, default).ok_or_else(|| {
+            anyhow!(
+                "Scripted answer '{}' is not one of {}",
+                input,
+                choices.join(", ")
+            )
+        })
+    }
+
+    fn ask(&self, question: &str) -> Result<String> {
+        Ok(self.next_answer(question)?.trim().to_string())
+    }
+}
+
+impl Reporter for Scripted {
+    fn info(&self, message: &str) {
+        self.messages.borrow_mut().push(message.to_string());
+    }
+
+    fn warn(&self, message: &str) {
+        self.warnings.borrow_mut().push(message.to_string());
+    }
+}
+
+fn parse_confirm(input: &str, default: bool) -> Option<bool> {
+    match input.trim().to_lowercase().as_str() {
+        "" => Some(default),
+        "y" | "yes" => Some(true),
+        "n" | "no" => Some(false),
+        _ => None,
+    }
+}
+
+/// Accepts a choice's full name or its first letter.
+fn parse_choice(input: &str, choices: &[&str], default: usize) -> Option<usize> {
+    let input = input.trim().to_lowercase();
+    if input.is_empty() {
+        return Some(default);
+    }
+
+    choices.iter().position(|choice| {
+        let choice = choice.to_lowercase();
+        choice == input || choice.chars().next().map(String::from) == Some(input.clone())
+    })
+}
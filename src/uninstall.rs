@@ -0,0 +1,173 @@
+use crate::config::Config;
+use crate::error::CapsyncError;
+use crate::lockfile::Lockfile;
+use crate::sync::{PlanEntry, PlannedAction, SyncManager, SyncPlan};
+use crate::ui::Reporter;
+use anyhow::{Context, anyhow};
+use git2::Repository;
+use std::path::{Component, Path, PathBuf};
+use std::time::SystemTime;
+
+#[derive(Debug, Clone, Default)]
+pub struct UninstallOptions {
+    pub slug: String,
+    /// Move the skill to a hidden backup in `skills_source` instead of deleting it.
+    pub backup: bool,
+}
+
+#[derive(Debug)]
+pub struct UninstallResult {
+    pub slug: String,
+    pub backup_path: Option<PathBuf>,
+    /// Per-skill links removed from destinations.
+    pub unlinked: Vec<PathBuf>,
+    /// Whether the skill had an entry in `capsync.lock`.
+    pub was_recorded: bool,
+}
+
+/// Plans removing a skill from `skills_source` and the per-skill links to it
+/// in every enabled destination, links first. Copy and hardlink destinations
+/// keep their mirror of the skill until the next `capsync sync`.
+pub fn plan_uninstall(
+    options: &UninstallOptions,
+    config: &Config,
+) -> Result<SyncPlan, CapsyncError> {
+    let skills_source = &config.skills_source;
+    let slug = options.slug.as_str();
+
+    let components: Vec<_> = Path::new(slug).components().collect();
+    if !matches!(components.as_slice(), [Component::Normal(_)]) {
+        return Err(CapsyncError::SkillNotFound(format!(
+            "Not a skill name: '{}'",
+            slug
+        )));
+    }
+
+    if Repository::open(skills_source).is_ok() {
+        return Err(anyhow!(
+            "Skills source is a git repository at {}. Remove the skill with git so the change can be committed.",
+            skills_source.display()
+        )
+        .into());
+    }
+
+    let skill_dir = skills_source.join(slug);
+    let recorded = Lockfile::load(skills_source)?.get(slug).is_some();
+    if !skill_dir.is_dir() && !recorded {
+        return Err(CapsyncError::SkillNotFound(format!(
+            "No skill named '{}' in {}",
+            slug,
+            skills_source.display()
+        )));
+    }
+
+    let mut plan = SyncManager::plan_unlink_skill(slug, config);
+
+    if skill_dir.is_dir() {
+        let action = if options.backup {
+            let timestamp = SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .context("Failed to get timestamp")?
+                .as_secs();
+            PlannedAction::Move {
+                from: skill_dir.clone(),
+                to: skills_source.join(format!(".{}.backup.{}", slug, timestamp)),
+            }
+        } else {
+            PlannedAction::DeleteDirectory {
+                path: skill_dir.clone(),
+            }
+        };
+
+        plan.entries.push(PlanEntry {
+            name: skill_entry_name(slug),
+            path: skill_dir,
+            actions: vec![action],
+            error: None,
+        });
+    }
+
+    Ok(plan)
+}
+
+fn skill_entry_name(slug: &str) -> String {
+    format!("skill {}", slug)
+}
+
+/// Removes an installed skill, its per-skill links, and its `capsync.lock`
+/// entry. The skill directory is only removed once every link to it is gone,
+/// so no destination is left pointing at a missing directory.
+pub fn uninstall_skill(
+    options: &UninstallOptions,
+    config: &Config,
+    reporter: &dyn Reporter,
+) -> Result<UninstallResult, CapsyncError> {
+    let plan = plan_uninstall(options, config)?;
+    let skill_entry = skill_entry_name(&options.slug);
+    let (skill_entries, link_entries): (Vec<_>, Vec<_>) = plan
+        .entries
+        .iter()
+        .cloned()
+        .partition(|entry| entry.name == skill_entry);
+
+    let mut result = SyncManager::apply(&SyncPlan {
+        entries: link_entries,
+    });
+    for (name, error) in &result.errors {
+        reporter.warn(&format!("Failed to remove {name}: {error}"));
+    }
+    if !result.errors.is_empty() {
+        return Err(anyhow!(
+            "Not removing '{}': {} destination(s) still link to it",
+            options.slug,
+            result.errors.len()
+        )
+        .into());
+    }
+
+    let skill_result = SyncManager::apply(&SyncPlan {
+        entries: skill_entries,
+    });
+    for (name, error) in &skill_result.errors {
+        reporter.warn(&format!("Failed to remove {name}: {error}"));
+    }
+    result.successful.extend(skill_result.successful);
+
+    let skill_dir = config.skills_source.join(&options.slug);
+    if skill_dir.exists() {
+        return Err(anyhow!(
+            "Failed to uninstall '{}': {} is still there",
+            options.slug,
+            skill_dir.display()
+        )
+        .into());
+    }
+
+    let mut lockfile = Lockfile::load(&config.skills_source)?;
+    let was_recorded = lockfile.remove(&options.slug).is_some();
+    if was_recorded {
+        lockfile.save(&config.skills_source)?;
+    }
+
+    let mut backup_path = None;
+    let mut unlinked = Vec::new();
+    for entry in &plan.entries {
+        if !result.successful.contains(&entry.name) {
+            continue;
+        }
+        for action in &entry.actions {
+            match action {
+                PlannedAction::Move { to, .. } => backup_path = Some(to.clone()),
+                PlannedAction::RemoveLink { path } => unlinked.push(path.clone()),
+                _ => {}
+            }
+        }
+    }
+
+    Ok(UninstallResult {
+        slug: options.slug.clone(),
+        backup_path,
+        unlinked,
+        was_recorded,
+    })
+}
@@ -0,0 +1,242 @@
+use crate::config::Config;
+use crate::error::CapsyncError;
+use crate::install::{
+    ExistingSkill, ResolvedInstallRef, SkillSelector, SourceTree, derive_skill_slug,
+    install_skill_from_checkout, resolve_local_source, resolve_skill_source,
+};
+use crate::lockfile::{LockedSkill, Lockfile, hash_skill_tree, short_version};
+use crate::ui::{NonInteractive, Reporter};
+use anyhow::{Result, anyhow};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Repository URL, branch, and pinned revision; skills sharing all three are
+/// read from one checkout.
+type CheckoutKey = (String, Option<String>, Option<String>);
+
+#[derive(Debug, Clone, Default)]
+pub struct UpdateOptions {
+    /// Slugs to update. Give these or set `all`, not both.
+    pub slugs: Vec<String>,
+    /// Update every skill in `capsync.lock`.
+    pub all: bool,
+    /// Replace skills whose files were edited since they were installed.
+    pub force: bool,
+    /// Report available updates without replacing anything.
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UpdateStatus {
+    UpToDate,
+    /// A newer commit exists upstream; only reported by dry runs.
+    Available {
+        from: String,
+        to: String,
+    },
+    Updated {
+        from: String,
+        to: String,
+    },
+    /// A newer commit exists, but the installed files were edited locally.
+    LocallyModified {
+        from: String,
+        to: String,
+    },
+    Failed(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkillUpdate {
+    pub slug: String,
+    pub status: UpdateStatus,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct UpdateResult {
+    pub skills: Vec<SkillUpdate>,
+}
+
+impl UpdateResult {
+    pub fn failures(&self) -> Vec<(String, String)> {
+        self.skills
+            .iter()
+            .filter_map(|skill| match &skill.status {
+                UpdateStatus::Failed(error) => Some((skill.slug.clone(), error.clone())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn report(&self, reporter: &dyn Reporter) {
+        for skill in &self.skills {
+            match &skill.status {
+                UpdateStatus::UpToDate => {
+                    reporter.info(&format!("  {}: up to date", skill.slug));
+                }
+                UpdateStatus::Available { from, to } => reporter.info(&format!(
+                    "  {}: update available ({} -> {})",
+                    skill.slug,
+                    short_version(from),
+                    short_version(to)
+                )),
+                UpdateStatus::Updated { from, to } => reporter.info(&format!(
+                    "  {}: updated ({} -> {})",
+                    skill.slug,
+                    short_version(from),
+                    short_version(to)
+                )),
+                UpdateStatus::LocallyModified { from, to } => reporter.warn(&format!(
+                    "  {}: update available ({} -> {}) but the installed files were edited; pass --force to replace them",
+                    skill.slug,
+                    short_version(from),
+                    short_version(to)
+                )),
+                UpdateStatus::Failed(error) => {
+                    reporter.warn(&format!("  {}: {}", skill.slug, error));
+                }
+            }
+        }
+    }
+}
+
+/// Re-fetches installed skills from the source recorded in `capsync.lock` and
+/// replaces those with a newer upstream commit.
+pub fn update_skills(
+    options: &UpdateOptions,
+    config: &Config,
+    reporter: &dyn Reporter,
+) -> Result<UpdateResult, CapsyncError> {
+    let skills_source = &config.skills_source;
+    let mut lockfile = Lockfile::load(skills_source)?;
+
+    let slugs = if options.all {
+        if !options.slugs.is_empty() {
+            return Err(anyhow!("Pass skill slugs or --all to update, not both").into());
+        }
+        lockfile.skills.keys().cloned().collect()
+    } else if options.slugs.is_empty() {
+        return Err(anyhow!("Name the skills to update, or pass --all to update every one").into());
+    } else {
+        for slug in &options.slugs {
+            if lockfile.get(slug).is_none() {
+                return Err(CapsyncError::SkillNotFound(format!(
+                    "Skill '{}' was not installed with 'capsync install', so it has no recorded source to update from",
+                    slug
+                )));
+            }
+        }
+        options.slugs.clone()
+    };
+
+    let mut checkouts: HashMap<CheckoutKey, SourceTree> = HashMap::new();
+    let mut result = UpdateResult::default();
+
+    for slug in slugs {
+        let Some(entry) = lockfile.get(&slug).cloned() else {
+            continue;
+        };
+
+        let status = match update_skill(
+            &slug,
+            &entry,
+            options,
+            skills_source,
+            &mut checkouts,
+            reporter,
+        ) {
+            Ok((status, Some(locked))) => {
+                lockfile.insert(&slug, locked);
+                lockfile.save(skills_source)?;
+                status
+            }
+            Ok((status, None)) => status,
+            Err(e) => UpdateStatus::Failed(format!("{:#}", e)),
+        };
+
+        result.skills.push(SkillUpdate { slug, status });
+    }
+
+    Ok(result)
+}
+
+fn update_skill(
+    slug: &str,
+    entry: &LockedSkill,
+    options: &UpdateOptions,
+    skills_source: &Path,
+    checkouts: &mut HashMap<CheckoutKey, SourceTree>,
+    reporter: &dyn Reporter,
+) -> Result<(UpdateStatus, Option<LockedSkill>)> {
+    let key = (
+        entry.repo_url.clone(),
+        entry.branch.clone(),
+        entry.revision.clone(),
+    );
+    if !checkouts.contains_key(&key) {
+        let local = if entry.is_local() {
+            resolve_local_source(&entry.repo_url)?
+        } else {
+            None
+        };
+        let tree = match local {
+            Some(local) => SourceTree::open_local(&local)?,
+            None => {
+                reporter.info(&format!("Fetching {}...", entry.repo_url));
+                SourceTree::clone_repository(
+                    &entry.repo_url,
+                    entry.branch.as_deref(),
+                    entry.revision.as_deref(),
+                )?
+            }
+        };
+        checkouts.insert(key.clone(), tree);
+    }
+    let tree = &checkouts[&key];
+
+    let resolved_reference = ResolvedInstallRef {
+        repo_url: entry.repo_url.clone(),
+        branch: entry.branch.clone(),
+        revision: entry.revision.clone(),
+        selector: SkillSelector::Path(entry.subpath.clone()),
+    };
+    let skill_source = resolve_skill_source(&tree.root, &resolved_reference)?;
+
+    let latest = tree.revision(&skill_source)?;
+    if latest == entry.source_version() {
+        return Ok((UpdateStatus::UpToDate, None));
+    }
+
+    let from = entry.source_version().to_string();
+    let installed_path = skills_source.join(slug);
+    if installed_path.exists()
+        && hash_skill_tree(&installed_path)? != entry.content_hash
+        && !options.force
+    {
+        return Ok((UpdateStatus::LocallyModified { from, to: latest }, None));
+    }
+
+    let upstream_slug = derive_skill_slug(&skill_source)?;
+    if upstream_slug != slug {
+        return Err(anyhow!(
+            "The skill at {} is now named '{}'; install it again under its new name",
+            entry.subpath.display(),
+            upstream_slug
+        ));
+    }
+
+    if options.dry_run {
+        return Ok((UpdateStatus::Available { from, to: latest }, None));
+    }
+
+    let installed = install_skill_from_checkout(
+        &tree.root,
+        &resolved_reference,
+        skills_source,
+        ExistingSkill::Replace,
+        &NonInteractive,
+    )?;
+    let locked = tree.locked_skill(&entry.reference, &resolved_reference, &installed)?;
+
+    Ok((UpdateStatus::Updated { from, to: latest }, Some(locked)))
+}
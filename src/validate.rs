@@ -0,0 +1,400 @@
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use yaml_rust2::{Yaml, YamlLoader};
+
+pub const MAX_NAME_LENGTH: usize = 64;
+pub const MAX_DESCRIPTION_LENGTH: usize = 1024;
+
+/// How serious a `doctor` finding or a skill validation problem is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+/// The YAML frontmatter between the leading `---` lines of a markdown file.
+#[derive(Debug, Clone)]
+pub struct Frontmatter {
+    fields: yaml_rust2::yaml::Hash,
+    key_lines: HashMap<String, usize>,
+    /// First line after the closing `---`.
+    body_line: usize,
+}
+
+/// Why a file's frontmatter could not be read. Lines are 1-based and count
+/// from the top of the file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FrontmatterError {
+    Unterminated,
+    Syntax { line: usize, message: String },
+    NotMapping,
+}
+
+impl fmt::Display for FrontmatterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FrontmatterError::Unterminated => {
+                write!(f, "frontmatter is not closed with a '---' line")
+            }
+            FrontmatterError::Syntax { message, .. } => {
+                write!(f, "frontmatter is not valid YAML: {}", message)
+            }
+            FrontmatterError::NotMapping => {
+                write!(f, "frontmatter must be a YAML mapping of fields")
+            }
+        }
+    }
+}
+
+impl Frontmatter {
+    /// Parses the frontmatter at the top of `content`. Returns `Ok(None)` when
+    /// the file does not start with a `---` line.
+    pub fn parse(content: &str) -> Result<Option<Self>, FrontmatterError> {
+        let mut lines = content.lines();
+        if lines.next().map(str::trim_end) != Some("---") {
+            return Ok(None);
+        }
+
+        let mut yaml_lines = Vec::new();
+        let mut closed = false;
+        for line in lines {
+            if line.trim_end() == "---" {
+                closed = true;
+                break;
+            }
+            yaml_lines.push(line);
+        }
+        if !closed {
+            return Err(FrontmatterError::Unterminated);
+        }
+
+        let documents = YamlLoader::load_from_str(&yaml_lines.join("\n")).map_err(|e| {
+            FrontmatterError::Syntax {
+                line: e.marker().line() + 1,
+                message: e.info().to_string(),
+            }
+        })?;
+
+        let fields = match documents.into_iter().next() {
+            Some(Yaml::Hash(fields)) => fields,
+            None => Default::default(),
+            Some(_) => return Err(FrontmatterError::NotMapping),
+        };
+
+        let mut key_lines = HashMap::new();
+        for (index, line) in yaml_lines.iter().enumerate() {
+            if line.starts_with([' ', '\t', '#', '-']) {
+                continue;
+            }
+            if let Some((key, _)) = line.split_once(':') {
+                let key = key.trim().trim_matches('"').trim_matches('\'');
+                key_lines.entry(key.to_string()).or_insert(index + 2);
+            }
+        }
+
+        Ok(Some(Self {
+            fields,
+            key_lines,
+            body_line: yaml_lines.len() + 3,
+        }))
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Yaml> {
+        self.fields.get(&Yaml::String(key.to_string()))
+    }
+
+    /// A field's value when it is a string.
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        self.get(key).and_then(Yaml::as_str)
+    }
+
+    /// The line a top-level field is on, or the opening `---` line when the
+    /// field is missing.
+    pub fn line_of(&self, key: &str) -> usize {
+        self.key_lines.get(key).copied().unwrap_or(1)
+    }
+}
+
+/// A problem with a skill, pointing at the file and line it was found on.
+#[derive(Debug, Clone, Serialize)]
+pub struct Problem {
+    pub severity: Severity,
+    pub path: PathBuf,
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+
+        match self.line {
+            Some(line) => write!(
+                f,
+                "{}:{}: {}: {}",
+                self.path.display(),
+                line,
+                severity,
+                self.message
+            ),
+            None => write!(f, "{}: {}: {}", self.path.display(), severity, self.message),
+        }
+    }
+}
+
+/// Checks a skill directory's `SKILL.md`: the frontmatter must parse and have
+/// a `name` and `description` within the length limits. The name should be
+/// lowercase words joined by hyphens and match the directory, and files linked
+/// from the body should exist. Only the first group are errors.
+pub fn validate_skill(skill_dir: &Path) -> Result<Vec<Problem>> {
+    let skill_markdown = skill_dir.join("SKILL.md");
+    let mut problems = Vec::new();
+    let mut report = |severity, line, message: String| {
+        problems.push(Problem {
+            severity,
+            path: skill_markdown.clone(),
+            line,
+            message,
+        })
+    };
+
+    if !skill_markdown.is_file() {
+        report(Severity::Error, None, "SKILL.md is missing".to_string());
+        return Ok(problems);
+    }
+
+    let content = fs::read_to_string(&skill_markdown)
+        .with_context(|| format!("Failed to read {}", skill_markdown.display()))?;
+
+    let frontmatter = match Frontmatter::parse(&content) {
+        Ok(Some(frontmatter)) => frontmatter,
+        Ok(None) => {
+            report(
+                Severity::Error,
+                Some(1),
+                "SKILL.md must start with YAML frontmatter between '---' lines".to_string(),
+            );
+            return Ok(problems);
+        }
+        Err(e) => {
+            let line = match &e {
+                FrontmatterError::Syntax { line, .. } => *line,
+                _ => 1,
+            };
+            report(Severity::Error, Some(line), e.to_string());
+            return Ok(problems);
+        }
+    };
+
+    let name_line = Some(frontmatter.line_of("name"));
+    match frontmatter.get("name") {
+        None => report(
+            Severity::Error,
+            name_line,
+            "frontmatter has no 'name'".to_string(),
+        ),
+        Some(Yaml::String(name)) if name.trim().is_empty() => {
+            report(Severity::Error, name_line, "'name' is empty".to_string())
+        }
+        Some(Yaml::String(name)) => {
+            if name.chars().count() > MAX_NAME_LENGTH {
+                report(
+                    Severity::Error,
+                    name_line,
+                    format!(
+                        "'name' is {} characters long; the limit is {}",
+                        name.chars().count(),
+                        MAX_NAME_LENGTH
+                    ),
+                );
+            }
+
+            if !is_valid_name(name) {
+                report(
+                    Severity::Warning,
+                    name_line,
+                    format!(
+                        "'name' should be lowercase letters, digits and single hyphens, like '{}'",
+                        crate::install::normalize_skill_slug(name)
+                    ),
+                );
+            }
+
+            let directory_name = skill_dir.canonicalize().ok().and_then(|dir| {
+                dir.file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+            });
+            if let Some(directory_name) = directory_name {
+                if directory_name != *name {
+                    report(
+                        Severity::Warning,
+                        name_line,
+                        format!(
+                            "'name' is '{}' but the directory is '{}'",
+                            name, directory_name
+                        ),
+                    );
+                }
+            }
+        }
+        Some(_) => report(
+            Severity::Error,
+            name_line,
+            "'name' must be a string".to_string(),
+        ),
+    }
+
+    let description_line = Some(frontmatter.line_of("description"));
+    match frontmatter.get("description") {
+        None => report(
+            Severity::Error,
+            description_line,
+            "frontmatter has no 'description'".to_string(),
+        ),
+        Some(Yaml::String(description)) if description.trim().is_empty() => report(
+            Severity::Error,
+            description_line,
+            "'description' is empty".to_string(),
+        ),
+        Some(Yaml::String(description)) if description.chars().count() > MAX_DESCRIPTION_LENGTH => {
+            report(
+                Severity::Error,
+                description_line,
+                format!(
+                    "'description' is {} characters long; the limit is {}",
+                    description.chars().count(),
+                    MAX_DESCRIPTION_LENGTH
+                ),
+            )
+        }
+        Some(Yaml::String(_)) => {}
+        Some(_) => report(
+            Severity::Error,
+            description_line,
+            "'description' must be a string".to_string(),
+        ),
+    }
+
+    for (line, target) in linked_files(&content, frontmatter.body_line) {
+        if !skill_dir.join(&target).exists() {
+            report(
+                Severity::Warning,
+                Some(line),
+                format!("links to {}, which does not exist", target),
+            );
+        }
+    }
+
+    Ok(problems)
+}
+
+/// Skill names are lowercase letters and digits in words joined by single hyphens.
+pub fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.split('-').all(|word| {
+            !word.is_empty()
+                && word
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+        })
+}
+
+/// Relative file targets of markdown links in the body, with their line,
+/// skipping fenced code blocks, URLs and in-page anchors.
+fn linked_files(content: &str, body_line: usize) -> Vec<(usize, String)> {
+    let mut links = Vec::new();
+    let mut in_fence = false;
+
+    for (index, line) in content.lines().enumerate().skip(body_line - 1) {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+
+        let mut rest = line;
+        while let Some(start) = rest.find("](") {
+            rest = &rest[start + 2..];
+            let Some(end) = rest.find(')') else {
+                break;
+            };
+            let target = rest[..end]
+                .split_whitespace()
+                .next()
+                .unwrap_or("")
+                .trim_matches(['<', '>']);
+            let target = target.split(['#', '?']).next().unwrap_or("");
+            rest = &rest[end..];
+
+            if target.is_empty() || target.starts_with('/') || target.contains("://") {
+                continue;
+            }
+            if target.starts_with("mailto:") {
+                continue;
+            }
+            links.push((index + 1, target.to_string()));
+        }
+    }
+
+    links
+}
+
+/// The problems found in every skill below a path.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ValidationReport {
+    pub skills_checked: usize,
+    pub problems: Vec<Problem>,
+}
+
+impl ValidationReport {
+    pub fn count(&self, severity: Severity) -> usize {
+        self.problems
+            .iter()
+            .filter(|problem| problem.severity == severity)
+            .count()
+    }
+}
+
+/// Validates `path` as a single skill when it is a skill directory or a
+/// `SKILL.md`, and otherwise every skill found below it.
+pub fn validate_path(path: &Path) -> Result<ValidationReport> {
+    let mut skill_dirs = if path.file_name().is_some_and(|name| name == "SKILL.md") {
+        vec![path.parent().unwrap_or(Path::new(".")).to_path_buf()]
+    } else if path.join("SKILL.md").is_file() {
+        vec![path.to_path_buf()]
+    } else if path.is_dir() {
+        crate::install::collect_skill_directories(path)?
+            .into_iter()
+            .filter(|dir| {
+                !dir.strip_prefix(path)
+                    .unwrap_or(dir)
+                    .components()
+                    .any(|part| part.as_os_str().to_string_lossy().starts_with('.'))
+            })
+            .collect()
+    } else {
+        return Err(anyhow::anyhow!("No skill found at {}", path.display()));
+    };
+
+    skill_dirs.sort();
+
+    let mut report = ValidationReport::default();
+    for skill_dir in skill_dirs {
+        report.skills_checked += 1;
+        report.problems.extend(validate_skill(&skill_dir)?);
+    }
+
+    Ok(report)
+}
@@ -0,0 +1,98 @@
+use capsync::config::load_config_from;
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+use tempfile::TempDir;
+
+fn capsync(home: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_capsync"))
+        .arg("--home")
+        .arg(home)
+        .args(args)
+        .env_remove("CAPSYNC_CONFIG")
+        .env("RUST_BACKTRACE", "0")
+        .stdin(Stdio::null())
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_init_with_flags_skips_prompts() {
+    let temp_dir = TempDir::new().unwrap();
+    let home = temp_dir.path();
+    fs::create_dir_all(home.join(".cursor")).unwrap();
+
+    let output = capsync(
+        home,
+        &[
+            "init",
+            "--skills-source",
+            "~/skills",
+            "--commands-source",
+            "~/commands",
+            "--enable",
+            "claude",
+            "--detect",
+        ],
+    );
+    assert!(output.status.success(), "{:?}", output);
+
+    let config = load_config_from(&home.join(".config/capsync/config.toml")).unwrap();
+    assert_eq!(config.skills_source, home.join("skills"));
+    assert_eq!(config.commands_source, Some(home.join("commands")));
+    let mut enabled: Vec<_> = config.destinations.keys().collect();
+    enabled.sort();
+    assert_eq!(enabled, ["claude", "cursor"]);
+}
+
+#[test]
+fn test_init_without_terminal_or_flags_fails() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let output = capsync(temp_dir.path(), &["init"]);
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("--skills-source"), "{}", stderr);
+    assert!(!temp_dir.path().join(".config/capsync/config.toml").exists());
+}
+
+#[test]
+fn test_init_requires_force_to_overwrite() {
+    let temp_dir = TempDir::new().unwrap();
+    let home = temp_dir.path();
+
+    assert!(
+        capsync(home, &["init", "--skills-source", "/tmp/first"])
+            .status
+            .success()
+    );
+    let output = capsync(home, &["init", "--skills-source", "/tmp/second"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--force"));
+
+    let output = capsync(home, &["init", "--skills-source", "/tmp/second", "--force"]);
+    assert!(output.status.success());
+    let config = load_config_from(&home.join(".config/capsync/config.toml")).unwrap();
+    assert_eq!(config.skills_source, Path::new("/tmp/second"));
+    assert!(config.destinations.is_empty());
+}
+
+#[test]
+fn test_init_rejects_unknown_tools() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let output = capsync(
+        temp_dir.path(),
+        &[
+            "init",
+            "--skills-source",
+            "/tmp/skills",
+            "--enable",
+            "not-a-tool",
+        ],
+    );
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("not-a-tool"));
+}
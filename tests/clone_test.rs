@@ -1,6 +1,9 @@
 use capsync::clone::{
-    get_remote_default_branch, get_remote_url, normalize_repo_identity, parse_repo_url,
+    CloneOptions, OnExisting, clone_skills, get_remote_default_branch, get_remote_url,
+    normalize_repo_identity, parse_repo_url,
 };
+use capsync::config::Config;
+use capsync::ui::Scripted;
 use git2::{Repository, Signature};
 use std::fs;
 use tempfile::tempdir;
@@ -148,3 +151,70 @@ fn test_get_remote_default_branch_uses_remote_head() {
     let default_branch = get_remote_default_branch(temp_dir.path().to_str().unwrap()).unwrap();
     assert_eq!(default_branch, "develop");
 }
+
+fn existing_source_config() -> (tempfile::TempDir, Config) {
+    let temp_dir = tempdir().unwrap();
+    let mut config = Config::for_home(temp_dir.path());
+    config.skills_source = temp_dir.path().join("skills");
+    fs::create_dir_all(&config.skills_source).unwrap();
+    fs::write(config.skills_source.join("keep.md"), "mine").unwrap();
+    (temp_dir, config)
+}
+
+#[test]
+fn test_clone_on_existing_abort_leaves_source_untouched() {
+    let (_temp_dir, config) = existing_source_config();
+    let options = CloneOptions {
+        repo: "owner/repo".to_string(),
+        branch: Some("main".to_string()),
+        on_existing: Some(OnExisting::Abort),
+        ..Default::default()
+    };
+
+    let error = clone_skills(&options, &config, &Scripted::new(), &Scripted::new())
+        .err()
+        .unwrap();
+
+    assert!(error.to_string().contains("Aborted"));
+    assert!(config.skills_source.join("keep.md").exists());
+}
+
+#[test]
+fn test_clone_on_existing_update_requires_a_clone_of_the_same_repo() {
+    let (_temp_dir, config) = existing_source_config();
+    let options = CloneOptions {
+        repo: "owner/repo".to_string(),
+        branch: Some("main".to_string()),
+        on_existing: Some(OnExisting::Update),
+        yes: true,
+        ..Default::default()
+    };
+
+    let error = clone_skills(&options, &config, &Scripted::new(), &Scripted::new())
+        .err()
+        .unwrap();
+
+    assert!(error.to_string().contains("--on-existing override"));
+    assert!(config.skills_source.join("keep.md").exists());
+}
+
+#[test]
+fn test_clone_asks_the_prompter_before_overriding_a_plain_directory() {
+    let (_temp_dir, config) = existing_source_config();
+    let options = CloneOptions {
+        repo: "owner/repo".to_string(),
+        branch: Some("main".to_string()),
+        ..Default::default()
+    };
+    let ui = Scripted::new().answer("n");
+
+    let error = clone_skills(&options, &config, &ui, &ui).err().unwrap();
+
+    assert!(error.to_string().contains("Aborted"));
+    assert_eq!(
+        ui.questions(),
+        ["Skills source exists but is not a git repository. Override?"]
+    );
+    assert!(ui.messages().contains(&"Using branch: main".to_string()));
+    assert!(config.skills_source.join("keep.md").exists());
+}
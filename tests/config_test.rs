@@ -1,5 +1,8 @@
-use capsync::config::{Config, get_config_path};
+use capsync::config::{Config, LinkMode, get_config_path, load_config_from, save_config_to};
+use capsync::paths::resolve_config_path;
+use capsync::tools::ToolRegistry;
 use std::fs;
+use std::path::PathBuf;
 use tempfile::TempDir;
 
 #[test]
@@ -133,3 +136,166 @@ path = "/home/user/.claude/skills"
     // Commands path should be None for old configs
     assert!(opencode.commands_path.is_none());
 }
+
+#[test]
+fn test_link_mode_defaults_to_symlink_and_parses_per_skill() {
+    let config: Config = toml::from_str(
+        r#"
+skills_source = "/tmp/skills"
+
+[destinations.claude]
+enabled = true
+skills_path = "/home/user/.claude/skills"
+
+[destinations.cursor]
+enabled = true
+skills_path = "/home/user/.cursor/skills"
+link_mode = "per-skill"
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        config.destinations.get("claude").unwrap().link_mode,
+        LinkMode::Symlink
+    );
+    assert_eq!(
+        config.destinations.get("cursor").unwrap().link_mode,
+        LinkMode::PerSkill
+    );
+
+    let serialized = toml::to_string_pretty(&config).unwrap();
+    assert_eq!(serialized.matches("link_mode").count(), 1);
+}
+
+#[test]
+fn test_link_mode_parses_copy_and_hardlink() {
+    let config: Config = toml::from_str(
+        r#"
+skills_source = "/tmp/skills"
+
+[destinations.cursor]
+enabled = true
+skills_path = "/home/user/.cursor/skills"
+link_mode = "copy"
+
+[destinations.codex]
+enabled = true
+skills_path = "/home/user/.codex/skills"
+link_mode = "hardlink"
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        config.destinations.get("cursor").unwrap().link_mode,
+        LinkMode::Copy
+    );
+    assert_eq!(
+        config.destinations.get("codex").unwrap().link_mode,
+        LinkMode::Hardlink
+    );
+}
+
+#[test]
+fn test_config_parses_custom_tools() {
+    let content = r#"
+skills_source = "/tmp/skills"
+
+[destinations.my-internal-agent]
+enabled = true
+skills_path = "/tmp/agent/skills"
+
+[custom_tools.my-internal-agent]
+detect_path = "~/.my-agent"
+skills_path = "~/.my-agent/skills"
+commands_path = "~/.my-agent/commands"
+"#;
+
+    let config: Config = toml::from_str(content).unwrap();
+    let custom = config.custom_tools.get("my-internal-agent").unwrap();
+    assert_eq!(
+        custom.skills_path,
+        std::path::PathBuf::from("~/.my-agent/skills")
+    );
+    assert!(config.validate().is_ok());
+
+    let home = std::path::Path::new("/tmp/capsync-home");
+    let registry =
+        ToolRegistry::load_in(home, &home.join("tools.toml"), &config.custom_tools).unwrap();
+    let tool = registry.get("my-internal-agent").unwrap();
+    assert_eq!(tool.skills_path, home.join(".my-agent/skills"));
+
+    let serialized = toml::to_string_pretty(&config).unwrap();
+    assert!(serialized.contains("[custom_tools.my-internal-agent]"));
+    assert!(
+        !toml::to_string_pretty(&Config::default())
+            .unwrap()
+            .contains("custom_tools")
+    );
+}
+
+#[test]
+fn test_config_default_host_for_install_shorthand() {
+    let config: Config = toml::from_str(
+        "skills_source = \"/tmp/skills\"\ndefault_host = \"gitlab.example.com\"\n[destinations]\n",
+    )
+    .unwrap();
+    assert_eq!(config.install_host(), "gitlab.example.com");
+    assert!(config.validate().is_ok());
+    assert_eq!(Config::default().install_host(), "github.com");
+    assert!(
+        !toml::to_string_pretty(&Config::default())
+            .unwrap()
+            .contains("default_host")
+    );
+
+    let mut invalid = config.clone();
+    invalid.default_host = Some("  ".to_string());
+    assert!(invalid.validate().is_err());
+}
+
+#[test]
+fn test_resolve_config_path_precedence() {
+    let home = PathBuf::from("/tmp/capsync-home");
+
+    assert_eq!(
+        resolve_config_path(None, None, &home),
+        home.join(".config/capsync/config.toml")
+    );
+    assert_eq!(
+        resolve_config_path(None, Some(PathBuf::from("/tmp/xdg")), &home),
+        PathBuf::from("/tmp/xdg/capsync/config.toml")
+    );
+    assert_eq!(
+        resolve_config_path(
+            Some(PathBuf::from("/tmp/explicit.toml")),
+            Some(PathBuf::from("/tmp/xdg")),
+            &home
+        ),
+        PathBuf::from("/tmp/explicit.toml")
+    );
+}
+
+#[test]
+fn test_config_for_home_and_explicit_config_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let home = temp_dir.path().join("home");
+    let config_path = temp_dir.path().join("nested/capsync.toml");
+
+    let mut config = Config::for_home(&home);
+    config.skills_source = temp_dir.path().join("skills");
+    assert_eq!(
+        config.destinations.get("claude").unwrap().skills_path,
+        home.join(".claude/skills")
+    );
+
+    save_config_to(&config, &config_path).unwrap();
+    let loaded = load_config_from(&config_path).unwrap();
+
+    assert_eq!(loaded.skills_source, config.skills_source);
+    assert_eq!(
+        loaded.destinations.get("cursor").unwrap().skills_path,
+        home.join(".cursor/skills")
+    );
+}
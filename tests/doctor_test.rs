@@ -0,0 +1,125 @@
+use capsync::config::{Config, LinkMode, save_config_to};
+use capsync::doctor::{Finding, SafeFix, apply_safe_fixes, diagnose};
+use capsync::validate::Severity;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn write_config(temp_dir: &TempDir, enabled: &[&str]) -> (Config, std::path::PathBuf) {
+    let mut config = Config::for_home(&temp_dir.path().join("home"));
+    config.skills_source = temp_dir.path().join("skills");
+    for name in enabled {
+        config.destinations.get_mut(*name).unwrap().enabled = true;
+    }
+
+    let config_path = temp_dir.path().join("config.toml");
+    save_config_to(&config, &config_path).unwrap();
+    (config, config_path)
+}
+
+fn findings_for<'a>(findings: &'a [Finding], check: &str) -> Vec<&'a Finding> {
+    findings
+        .iter()
+        .filter(|finding| finding.check == check)
+        .collect()
+}
+
+fn write_skill(source: &Path, name: &str, content: &str) {
+    fs::create_dir_all(source.join(name)).unwrap();
+    fs::write(source.join(name).join("SKILL.md"), content).unwrap();
+}
+
+#[test]
+fn test_doctor_reports_unloadable_config() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("config.toml");
+    fs::write(&config_path, "skills_source = [").unwrap();
+
+    let findings = diagnose(&config_path);
+
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].severity, Severity::Error);
+    assert_eq!(findings[0].check, "config");
+    assert_eq!(findings[0].suggestion.as_deref(), Some("capsync init"));
+}
+
+#[test]
+fn test_doctor_fixes_missing_source_and_unsynced_destination() {
+    let temp_dir = TempDir::new().unwrap();
+    let (config, config_path) = write_config(&temp_dir, &["claude"]);
+
+    let findings = diagnose(&config_path);
+    let sources = findings_for(&findings, "sources");
+    assert_eq!(sources.len(), 1);
+    assert_eq!(
+        sources[0].safe_fix,
+        Some(SafeFix::CreateDirectory {
+            path: config.skills_source.clone()
+        })
+    );
+    let destinations = findings_for(&findings, "destinations");
+    assert_eq!(destinations[0].safe_fix, Some(SafeFix::Sync));
+
+    let result = apply_safe_fixes(&findings, &config).unwrap().unwrap();
+    assert_eq!(result.successful, ["claude"]);
+    assert!(config.skills_source.is_dir());
+
+    let findings = diagnose(&config_path);
+    assert!(findings_for(&findings, "sources").is_empty());
+    assert!(findings_for(&findings, "destinations").is_empty());
+}
+
+#[test]
+fn test_doctor_flags_foreign_directories_without_a_safe_fix() {
+    let temp_dir = TempDir::new().unwrap();
+    let (config, config_path) = write_config(&temp_dir, &["claude"]);
+    fs::create_dir_all(&config.skills_source).unwrap();
+    let claude = &config.destinations["claude"].skills_path;
+    fs::create_dir_all(claude).unwrap();
+    fs::write(claude.join("notes.md"), "mine").unwrap();
+
+    let findings = diagnose(&config_path);
+    let destinations = findings_for(&findings, "destinations");
+
+    assert_eq!(destinations.len(), 1);
+    assert_eq!(destinations[0].severity, Severity::Error);
+    assert_eq!(
+        destinations[0].suggestion.as_deref(),
+        Some("capsync adopt claude")
+    );
+    assert!(destinations[0].safe_fix.is_none());
+}
+
+#[test]
+fn test_doctor_checks_shared_paths_and_skill_files() {
+    let temp_dir = TempDir::new().unwrap();
+    let (mut config, config_path) = write_config(&temp_dir, &["claude", "claude-code"]);
+    config
+        .destinations
+        .get_mut("claude-code")
+        .unwrap()
+        .link_mode = LinkMode::PerSkill;
+    save_config_to(&config, &config_path).unwrap();
+
+    write_skill(
+        &config.skills_source,
+        "good",
+        "---\nname: good\ndescription: Works\n---\n",
+    );
+    write_skill(&config.skills_source, "nameless", "# No frontmatter\n");
+    fs::create_dir_all(config.skills_source.join("empty")).unwrap();
+
+    let findings = diagnose(&config_path);
+
+    let shared = findings_for(&findings, "shared-paths");
+    assert_eq!(shared.len(), 1);
+    assert!(shared[0].message.contains("claude and claude-code"));
+
+    let skills: Vec<_> = findings_for(&findings, "skills")
+        .iter()
+        .map(|finding| finding.message.clone())
+        .collect();
+    assert_eq!(skills.len(), 2);
+    assert!(skills[0].ends_with("empty has no SKILL.md"));
+    assert!(skills[1].contains("nameless has a SKILL.md without a name"));
+}
@@ -0,0 +1,96 @@
+use capsync::clone::{CloneOptions, OnExisting, clone_skills};
+use capsync::config::{Config, load_config_from};
+use capsync::error::CapsyncError;
+use capsync::install::{ExistingSkill, InstallOptions, install_skill};
+use capsync::sync::{SourceKind, SyncManager};
+use capsync::ui::Scripted;
+use std::fs;
+use std::process::{Command, Stdio};
+use tempfile::TempDir;
+
+#[test]
+fn test_load_config_distinguishes_missing_and_malformed_files() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("config.toml");
+
+    let missing = load_config_from(&config_path).unwrap_err();
+    assert!(matches!(missing, CapsyncError::ConfigNotFound { .. }));
+
+    fs::write(&config_path, "skills_source = [").unwrap();
+    let malformed = load_config_from(&config_path).unwrap_err();
+    assert!(matches!(malformed, CapsyncError::ConfigParse { .. }));
+
+    fs::write(&config_path, "skills_source = \"\"\n[destinations]\n").unwrap();
+    let invalid = load_config_from(&config_path).unwrap_err();
+    assert!(matches!(invalid, CapsyncError::ConfigInvalid(_)));
+    assert_eq!(invalid.exit_code(), 3);
+}
+
+#[test]
+fn test_library_calls_return_typed_errors() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut config = Config::for_home(temp_dir.path());
+    config.skills_source = temp_dir.path().join("missing");
+
+    let error = SyncManager::plan_skills(&config).unwrap_err();
+    assert!(matches!(
+        error,
+        CapsyncError::SourceMissing {
+            kind: SourceKind::Skills,
+            ..
+        }
+    ));
+    assert_eq!(error.exit_code(), 4);
+
+    let error = SyncManager::plan_remove("not-a-tool", &config).unwrap_err();
+    assert!(matches!(error, CapsyncError::UnknownDestination(_)));
+
+    fs::create_dir_all(&config.skills_source).unwrap();
+    let options = InstallOptions {
+        reference: "owner/repo".to_string(),
+        existing: ExistingSkill::Prompt,
+        revision: None,
+    };
+    let error = install_skill(&options, &config, &Scripted::new(), &Scripted::new()).unwrap_err();
+    assert!(matches!(error, CapsyncError::InvalidReference(_)));
+    assert_eq!(error.exit_code(), 5);
+
+    let options = CloneOptions {
+        repo: "owner/repo".to_string(),
+        branch: Some("main".to_string()),
+        on_existing: Some(OnExisting::Abort),
+        ..Default::default()
+    };
+    let error = clone_skills(&options, &config, &Scripted::new(), &Scripted::new())
+        .err()
+        .unwrap();
+    assert!(matches!(error, CapsyncError::Aborted));
+    assert_eq!(error.exit_code(), 7);
+}
+
+#[test]
+fn test_find_looks_through_context() {
+    let error = anyhow::Error::from(CapsyncError::OutOfSync { count: 2 }).context("status");
+
+    let found = CapsyncError::find(&error).unwrap();
+    assert!(matches!(found, CapsyncError::OutOfSync { count: 2 }));
+    assert_eq!(found.exit_code(), 9);
+    assert!(CapsyncError::find(&anyhow::anyhow!("plain")).is_none());
+}
+
+#[test]
+fn test_binary_exits_with_error_code() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_capsync"))
+        .arg("--home")
+        .arg(temp_dir.path())
+        .arg("sync")
+        .env_remove("CAPSYNC_CONFIG")
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(3));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("capsync init"));
+}
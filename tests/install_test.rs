@@ -1,8 +1,13 @@
 use capsync::config::Config;
 use capsync::install::{
-    InstallOptions, InstallResult, ResolvedInstallRef, SkillSelector, install_skill,
-    install_skill_from_checkout, normalize_skill_slug, resolve_install_ref,
+    ExistingSkill, InstallOptions, InstallResult, ResolvedInstallRef, ResolvedRepoRef,
+    SkillInstallStatus, SkillSelector, SkillSet, install_skill, install_skill_from_checkout,
+    install_skills_from_checkout, normalize_skill_slug, resolve_install_ref,
+    resolve_install_ref_with_host, resolve_local_source, resolve_repo_ref,
+    resolve_repo_ref_with_host,
 };
+use capsync::lockfile::Lockfile;
+use capsync::ui::Scripted;
 use std::fs;
 #[cfg(unix)]
 use std::os::unix::fs::symlink;
@@ -145,6 +150,142 @@ fn test_resolve_install_ref_rejects_repo_only_reference() {
     );
 }
 
+#[test]
+fn test_resolve_install_ref_tree_urls_on_other_forges() {
+    let cases = [
+        (
+            "https://gitlab.example.com/platform/ai/skills/-/tree/main/skills/review",
+            "https://gitlab.example.com/platform/ai/skills.git",
+            "main",
+            "skills/review",
+        ),
+        (
+            "https://gitea.example.com/team/skills/src/branch/release%2F2/skills/review/",
+            "https://gitea.example.com/team/skills.git",
+            "release/2",
+            "skills/review",
+        ),
+        (
+            "https://bitbucket.org/team/skills/src/main/review",
+            "https://bitbucket.org/team/skills.git",
+            "main",
+            "review",
+        ),
+        (
+            "https://github.example.com/team/skills/tree/main/review",
+            "https://github.example.com/team/skills.git",
+            "main",
+            "review",
+        ),
+    ];
+
+    for (reference, repo_url, branch, path) in cases {
+        let resolved = resolve_install_ref(reference).unwrap();
+        assert_eq!(resolved.repo_url, repo_url, "{reference}");
+        assert_eq!(resolved.branch.as_deref(), Some(branch), "{reference}");
+        assert_eq!(
+            resolved.selector,
+            SkillSelector::Path(PathBuf::from(path)),
+            "{reference}"
+        );
+    }
+
+    let error =
+        resolve_install_ref("https://gitlab.example.com/platform/skills/-/tree/main").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "GitLab tree URLs must point to a concrete skill directory"
+    );
+
+    let repository =
+        resolve_repo_ref("https://gitlab.example.com/platform/ai/skills/-/tree/dev").unwrap();
+    assert_eq!(
+        repository.repo_url,
+        "https://gitlab.example.com/platform/ai/skills.git"
+    );
+    assert_eq!(repository.branch.as_deref(), Some("dev"));
+    assert_eq!(
+        resolve_repo_ref("https://gitlab.example.com/platform/ai/skills")
+            .unwrap()
+            .repo_url,
+        "https://gitlab.example.com/platform/ai/skills.git"
+    );
+}
+
+#[test]
+fn test_resolve_install_ref_shorthand_uses_the_default_host() {
+    let resolved =
+        resolve_install_ref_with_host("team/skills/review", "gitea.example.com").unwrap();
+    assert_eq!(
+        resolved.repo_url,
+        "https://gitea.example.com/team/skills.git"
+    );
+
+    let resolved =
+        resolve_install_ref_with_host("team/skills/review", "https://git.example.com/").unwrap();
+    assert_eq!(resolved.repo_url, "https://git.example.com/team/skills.git");
+
+    let skills_sh =
+        resolve_install_ref_with_host("https://skills.sh/team/skills/review", "gitea.example.com")
+            .unwrap();
+    assert_eq!(skills_sh.repo_url, "https://github.com/team/skills.git");
+
+    let repository = resolve_repo_ref_with_host("team/skills", "gitlab.example.com").unwrap();
+    assert_eq!(
+        repository.repo_url,
+        "https://gitlab.example.com/team/skills.git"
+    );
+}
+
+#[test]
+fn test_resolve_install_ref_pins_a_tag_or_commit() {
+    let resolved = resolve_install_ref("vercel-labs/skills/find-skills@v1.3").unwrap();
+    assert_eq!(
+        resolved.repo_url,
+        "https://github.com/vercel-labs/skills.git"
+    );
+    assert_eq!(resolved.branch, None);
+    assert_eq!(resolved.revision.as_deref(), Some("v1.3"));
+    assert_eq!(
+        resolved.selector,
+        SkillSelector::Slug("find-skills".to_string())
+    );
+
+    let resolved = resolve_install_ref("owner/repo/skills/review@release/2.0").unwrap();
+    assert_eq!(resolved.revision.as_deref(), Some("release/2.0"));
+    assert_eq!(
+        resolved.selector,
+        SkillSelector::Path(PathBuf::from("skills/review"))
+    );
+
+    let resolved = resolve_install_ref("https://skills.sh/owner/repo/review@abc1234").unwrap();
+    assert_eq!(resolved.revision.as_deref(), Some("abc1234"));
+
+    let commit = "0123456789abcdef0123456789abcdef01234567";
+    let resolved = resolve_install_ref(&format!(
+        "https://github.com/owner/repo/tree/{commit}/review"
+    ))
+    .unwrap();
+    assert_eq!(resolved.branch, None);
+    assert_eq!(resolved.revision.as_deref(), Some(commit));
+
+    let resolved =
+        resolve_install_ref("https://gitea.example.com/owner/repo/src/tag/v2/skills/review")
+            .unwrap();
+    assert_eq!(resolved.branch, None);
+    assert_eq!(resolved.revision.as_deref(), Some("v2"));
+
+    let repository = resolve_repo_ref("owner/repo@v2").unwrap();
+    assert_eq!(repository.repo_url, "https://github.com/owner/repo.git");
+    assert_eq!(repository.revision.as_deref(), Some("v2"));
+
+    let error = resolve_install_ref("owner/repo/review@").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Name a tag or commit after '@', like owner/repo/skill@v1.0"
+    );
+}
+
 #[test]
 fn test_normalize_skill_slug() {
     assert_eq!(normalize_skill_slug("Find Skills"), "find-skills");
@@ -164,11 +305,18 @@ fn test_install_skill_from_checkout_by_slug_uses_skill_name_slug() {
     let resolved = ResolvedInstallRef {
         repo_url: "https://github.com/vercel-labs/skills.git".to_string(),
         branch: None,
+        revision: None,
         selector: SkillSelector::Slug("find-skills".to_string()),
     };
 
-    let result =
-        install_skill_from_checkout(checkout_dir.path(), &resolved, target_dir.path()).unwrap();
+    let result = install_skill_from_checkout(
+        checkout_dir.path(),
+        &resolved,
+        target_dir.path(),
+        ExistingSkill::Prompt,
+        &Scripted::new(),
+    )
+    .unwrap();
 
     assert_eq!(result.skill_slug, "find-skills");
     assert!(!result.replaced_existing);
@@ -187,11 +335,18 @@ fn test_install_skill_from_checkout_prefers_skill_name_for_slug() {
     let resolved = ResolvedInstallRef {
         repo_url: "https://github.com/vercel-labs/skills.git".to_string(),
         branch: None,
+        revision: None,
         selector: SkillSelector::Path(PathBuf::from("skills/find-skill-files")),
     };
 
-    let result =
-        install_skill_from_checkout(checkout_dir.path(), &resolved, target_dir.path()).unwrap();
+    let result = install_skill_from_checkout(
+        checkout_dir.path(),
+        &resolved,
+        target_dir.path(),
+        ExistingSkill::Prompt,
+        &Scripted::new(),
+    )
+    .unwrap();
 
     assert_eq!(result.skill_slug, "find-skills");
     assert_eq!(result.installed_path, target_dir.path().join("find-skills"));
@@ -208,11 +363,18 @@ fn test_install_skill_from_checkout_by_explicit_path() {
     let resolved = ResolvedInstallRef {
         repo_url: "https://github.com/vercel-labs/skills.git".to_string(),
         branch: Some("main".to_string()),
+        revision: None,
         selector: SkillSelector::Path(PathBuf::from("packages/frontend-design")),
     };
 
-    let result =
-        install_skill_from_checkout(checkout_dir.path(), &resolved, target_dir.path()).unwrap();
+    let result = install_skill_from_checkout(
+        checkout_dir.path(),
+        &resolved,
+        target_dir.path(),
+        ExistingSkill::Prompt,
+        &Scripted::new(),
+    )
+    .unwrap();
 
     assert_eq!(result.skill_slug, "frontend-design");
     assert!(result.installed_path.join("SKILL.md").exists());
@@ -237,11 +399,18 @@ fn test_install_skill_from_checkout_rejects_parent_dir_path() {
     let resolved = ResolvedInstallRef {
         repo_url: "https://github.com/vercel-labs/skills.git".to_string(),
         branch: None,
+        revision: None,
         selector: SkillSelector::Path(PathBuf::from("../escaped-skill-for-test")),
     };
 
-    let error =
-        install_skill_from_checkout(checkout_dir.path(), &resolved, target_dir.path()).unwrap_err();
+    let error = install_skill_from_checkout(
+        checkout_dir.path(),
+        &resolved,
+        target_dir.path(),
+        ExistingSkill::Prompt,
+        &Scripted::new(),
+    )
+    .unwrap_err();
     assert!(
         error
             .to_string()
@@ -259,11 +428,18 @@ fn test_install_skill_from_checkout_errors_on_missing_skill() {
     let resolved = ResolvedInstallRef {
         repo_url: "https://github.com/vercel-labs/skills.git".to_string(),
         branch: None,
+        revision: None,
         selector: SkillSelector::Slug("missing-skill".to_string()),
     };
 
-    let error =
-        install_skill_from_checkout(checkout_dir.path(), &resolved, target_dir.path()).unwrap_err();
+    let error = install_skill_from_checkout(
+        checkout_dir.path(),
+        &resolved,
+        target_dir.path(),
+        ExistingSkill::Prompt,
+        &Scripted::new(),
+    )
+    .unwrap_err();
     assert!(
         error
             .to_string()
@@ -290,11 +466,18 @@ fn test_install_skill_from_checkout_errors_on_ambiguous_slug() {
     let resolved = ResolvedInstallRef {
         repo_url: "https://github.com/vercel-labs/skills.git".to_string(),
         branch: None,
+        revision: None,
         selector: SkillSelector::Slug("frontend-design".to_string()),
     };
 
-    let error =
-        install_skill_from_checkout(checkout_dir.path(), &resolved, target_dir.path()).unwrap_err();
+    let error = install_skill_from_checkout(
+        checkout_dir.path(),
+        &resolved,
+        target_dir.path(),
+        ExistingSkill::Prompt,
+        &Scripted::new(),
+    )
+    .unwrap_err();
     assert!(error.to_string().contains("Found multiple skills matching"));
 }
 
@@ -308,15 +491,24 @@ fn test_install_result_reports_installed_path() {
 
     let options = InstallOptions {
         reference: "vercel-labs/skills/find-skills".to_string(),
+        existing: ExistingSkill::Prompt,
+        revision: None,
     };
     let resolved = ResolvedInstallRef {
         repo_url: "https://github.com/vercel-labs/skills.git".to_string(),
         branch: None,
+        revision: None,
         selector: SkillSelector::Slug("find-skills".to_string()),
     };
 
-    let result: InstallResult =
-        install_skill_from_checkout(checkout_dir.path(), &resolved, target_dir.path()).unwrap();
+    let result: InstallResult = install_skill_from_checkout(
+        checkout_dir.path(),
+        &resolved,
+        target_dir.path(),
+        ExistingSkill::Prompt,
+        &Scripted::new(),
+    )
+    .unwrap();
 
     assert_eq!(options.reference, "vercel-labs/skills/find-skills");
     assert_eq!(result.skill_slug, "find-skills");
@@ -333,13 +525,17 @@ fn test_install_skill_rejects_git_repo_skills_source() {
         skills_source: repository_dir.path().to_path_buf(),
         commands_source: None,
         destinations: Config::default().destinations,
+        custom_tools: Default::default(),
+        default_host: None,
     };
 
     let options = InstallOptions {
         reference: "vercel-labs/skills/find-skills".to_string(),
+        existing: ExistingSkill::Prompt,
+        revision: None,
     };
 
-    let error = install_skill(&options, &config).unwrap_err();
+    let error = install_skill(&options, &config, &Scripted::new(), &Scripted::new()).unwrap_err();
     assert!(
         error
             .to_string()
@@ -365,11 +561,18 @@ fn test_install_skill_from_checkout_rejects_symlinks() {
     let resolved = ResolvedInstallRef {
         repo_url: "https://github.com/vercel-labs/skills.git".to_string(),
         branch: None,
+        revision: None,
         selector: SkillSelector::Slug("find-skills".to_string()),
     };
 
-    let error =
-        install_skill_from_checkout(checkout_dir.path(), &resolved, target_dir.path()).unwrap_err();
+    let error = install_skill_from_checkout(
+        checkout_dir.path(),
+        &resolved,
+        target_dir.path(),
+        ExistingSkill::Prompt,
+        &Scripted::new(),
+    )
+    .unwrap_err();
     assert!(
         error
             .to_string()
@@ -395,11 +598,18 @@ fn test_install_skill_from_checkout_cleans_staging_dir_on_copy_failure() {
     let resolved = ResolvedInstallRef {
         repo_url: "https://github.com/vercel-labs/skills.git".to_string(),
         branch: None,
+        revision: None,
         selector: SkillSelector::Slug("find-skills".to_string()),
     };
 
-    let error =
-        install_skill_from_checkout(checkout_dir.path(), &resolved, target_dir.path()).unwrap_err();
+    let error = install_skill_from_checkout(
+        checkout_dir.path(),
+        &resolved,
+        target_dir.path(),
+        ExistingSkill::Prompt,
+        &Scripted::new(),
+    )
+    .unwrap_err();
     assert!(
         error
             .to_string()
@@ -408,3 +618,369 @@ fn test_install_skill_from_checkout_cleans_staging_dir_on_copy_failure() {
     assert!(!target_dir.path().join("find-skills").exists());
     assert_eq!(fs::read_dir(target_dir.path()).unwrap().count(), 0);
 }
+
+#[test]
+fn test_install_skill_from_checkout_replaces_or_skips_existing_without_prompting() {
+    let checkout_dir = tempdir().unwrap();
+    let target_dir = tempdir().unwrap();
+
+    let skill_dir = checkout_dir.path().join("skills").join("find-skills");
+    write_skill(&skill_dir, "Find Skills", "Locate useful skills");
+    let installed = target_dir.path().join("find-skills");
+    fs::create_dir_all(&installed).unwrap();
+    fs::write(installed.join("SKILL.md"), "old").unwrap();
+
+    let resolved = ResolvedInstallRef {
+        repo_url: "https://github.com/vercel-labs/skills.git".to_string(),
+        branch: None,
+        revision: None,
+        selector: SkillSelector::Slug("find-skills".to_string()),
+    };
+
+    let skipped = install_skill_from_checkout(
+        checkout_dir.path(),
+        &resolved,
+        target_dir.path(),
+        ExistingSkill::Skip,
+        &Scripted::new(),
+    )
+    .unwrap();
+    assert!(skipped.skipped_existing);
+    assert!(!skipped.replaced_existing);
+    assert_eq!(
+        fs::read_to_string(installed.join("SKILL.md")).unwrap(),
+        "old"
+    );
+
+    let replaced = install_skill_from_checkout(
+        checkout_dir.path(),
+        &resolved,
+        target_dir.path(),
+        ExistingSkill::Replace,
+        &Scripted::new(),
+    )
+    .unwrap();
+    assert!(replaced.replaced_existing);
+    assert!(!replaced.skipped_existing);
+    assert_ne!(
+        fs::read_to_string(installed.join("SKILL.md")).unwrap(),
+        "old"
+    );
+}
+
+#[test]
+fn test_install_skill_from_checkout_asks_the_prompter_before_replacing() {
+    let checkout_dir = tempdir().unwrap();
+    let target_dir = tempdir().unwrap();
+
+    let skill_dir = checkout_dir.path().join("skills").join("find-skills");
+    write_skill(&skill_dir, "Find Skills", "Locate useful skills");
+    fs::create_dir_all(target_dir.path().join("find-skills")).unwrap();
+
+    let resolved = ResolvedInstallRef {
+        repo_url: "https://github.com/vercel-labs/skills.git".to_string(),
+        branch: None,
+        revision: None,
+        selector: SkillSelector::Slug("find-skills".to_string()),
+    };
+
+    let declined = Scripted::new().answer("n");
+    let error = install_skill_from_checkout(
+        checkout_dir.path(),
+        &resolved,
+        target_dir.path(),
+        ExistingSkill::Prompt,
+        &declined,
+    )
+    .unwrap_err();
+    assert!(error.to_string().contains("Aborted"));
+    assert!(declined.questions()[0].contains("Replace it?"));
+
+    let accepted = Scripted::new().answer("y");
+    let result = install_skill_from_checkout(
+        checkout_dir.path(),
+        &resolved,
+        target_dir.path(),
+        ExistingSkill::Prompt,
+        &accepted,
+    )
+    .unwrap();
+    assert!(result.replaced_existing);
+}
+
+#[test]
+fn test_resolve_repo_ref_accepts_repository_references() {
+    let expected = ResolvedRepoRef {
+        repo_url: "https://github.com/vercel-labs/skills.git".to_string(),
+        branch: None,
+        revision: None,
+    };
+    for reference in [
+        "vercel-labs/skills",
+        "https://github.com/vercel-labs/skills/",
+        "https://github.com/vercel-labs/skills.git",
+        "https://skills.sh/vercel-labs/skills",
+    ] {
+        assert_eq!(
+            resolve_repo_ref(reference).unwrap(),
+            expected,
+            "{reference}"
+        );
+    }
+
+    let branch =
+        resolve_repo_ref("https://github.com/vercel-labs/skills/tree/feature%2Fx").unwrap();
+    assert_eq!(branch.branch.as_deref(), Some("feature/x"));
+
+    let error = resolve_repo_ref("vercel-labs/skills/find-skills").unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .contains("repository reference like owner/repo")
+    );
+}
+
+fn team_repository() -> tempfile::TempDir {
+    let checkout_dir = tempdir().unwrap();
+    let skills = checkout_dir.path().join("skills");
+    write_skill(
+        &skills.join("find-skills"),
+        "find-skills",
+        "Locate useful skills",
+    );
+    write_skill(&skills.join("review"), "review", "Review code");
+    write_skill(&skills.join("review-copy"), "review", "Review code again");
+    fs::create_dir_all(skills.join("broken")).unwrap();
+    fs::write(skills.join("broken").join("SKILL.md"), "# no frontmatter\n").unwrap();
+    write_skill(
+        &checkout_dir.path().join(".github").join("ci"),
+        "ci",
+        "Hidden",
+    );
+    checkout_dir
+}
+
+#[test]
+fn test_install_skills_from_checkout_installs_all_and_reports_failures() {
+    let checkout_dir = team_repository();
+    let target_dir = tempdir().unwrap();
+    let repository = resolve_repo_ref("owner/team-skills").unwrap();
+
+    let result = install_skills_from_checkout(
+        checkout_dir.path(),
+        &repository,
+        &SkillSet::All,
+        target_dir.path(),
+        ExistingSkill::Prompt,
+        &Scripted::new(),
+    )
+    .unwrap();
+
+    let names: Vec<_> = result.skills.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(
+        names,
+        vec![
+            "skills/broken",
+            "skills/find-skills",
+            "skills/review",
+            "skills/review-copy"
+        ]
+    );
+
+    let installed: Vec<_> = result.installed().map(|r| r.skill_slug.as_str()).collect();
+    assert_eq!(installed, vec!["find-skills", "review"]);
+    assert!(target_dir.path().join("review").join("SKILL.md").exists());
+    assert!(!target_dir.path().join("broken").exists());
+    assert!(!target_dir.path().join("ci").exists());
+
+    let failures = result.failures();
+    assert_eq!(failures.len(), 2);
+    assert_eq!(failures[0].0, "skills/broken");
+    assert!(failures[0].1.contains("frontmatter"));
+    assert_eq!(failures[1].0, "skills/review-copy");
+    assert!(failures[1].1.contains("the same name as skills/review"));
+}
+
+#[test]
+fn test_install_skills_from_checkout_installs_named_skills_and_keeps_going() {
+    let checkout_dir = team_repository();
+    let target_dir = tempdir().unwrap();
+    let repository = resolve_repo_ref("owner/team-skills").unwrap();
+    fs::create_dir_all(target_dir.path().join("find-skills")).unwrap();
+
+    let declined = Scripted::new().answer("n");
+    let result = install_skills_from_checkout(
+        checkout_dir.path(),
+        &repository,
+        &SkillSet::Named(vec![
+            "Find Skills".to_string(),
+            "missing".to_string(),
+            "review-copy".to_string(),
+        ]),
+        target_dir.path(),
+        ExistingSkill::Prompt,
+        &declined,
+    )
+    .unwrap();
+
+    assert_eq!(result.skills.len(), 3);
+    match &result.skills[0].status {
+        SkillInstallStatus::Installed(installed) => assert!(installed.skipped_existing),
+        other => panic!("expected a skipped install, got {other:?}"),
+    }
+    assert!(declined.questions()[0].contains("Replace it?"));
+    assert_eq!(result.failures().len(), 1);
+    assert_eq!(result.failures()[0].0, "missing");
+    assert!(target_dir.path().join("review").exists());
+}
+
+#[test]
+fn test_resolve_local_source_only_claims_local_references() {
+    let root = tempdir().unwrap();
+    let skill_dir = root.path().join("my skill");
+    write_skill(&skill_dir, "my-skill", "Local skill");
+    let canonical = fs::canonicalize(&skill_dir).unwrap();
+
+    let by_path = resolve_local_source(skill_dir.to_str().unwrap())
+        .unwrap()
+        .unwrap();
+    assert_eq!(by_path.path, canonical);
+    assert!(by_path.url().starts_with("file://"));
+    assert!(by_path.url().ends_with("/my%20skill"));
+
+    let by_url = resolve_local_source(&by_path.url()).unwrap().unwrap();
+    assert_eq!(by_url, by_path);
+
+    for remote in [
+        "owner/repo/skill",
+        "https://github.com/owner/repo/tree/main/skill",
+    ] {
+        assert!(resolve_local_source(remote).unwrap().is_none(), "{remote}");
+    }
+
+    let missing = resolve_local_source("./does-not-exist.zip").unwrap_err();
+    assert!(missing.to_string().contains("No such file or directory"));
+    let text_file = root.path().join("notes.txt");
+    fs::write(&text_file, "notes").unwrap();
+    assert!(resolve_local_source(text_file.to_str().unwrap()).is_err());
+}
+
+#[test]
+fn test_resolve_local_source_rejects_malformed_escapes() {
+    for reference in [
+        "file:///tmp/%aé",
+        "file:///tmp/%zz",
+        "file:///tmp/%4",
+        "file:///tmp/%ff",
+    ] {
+        let error = resolve_local_source(reference).unwrap_err();
+        assert!(
+            error.to_string().contains("file URL"),
+            "{reference}: {error}"
+        );
+    }
+
+    assert!(resolve_install_ref("https://github.com/owner/repo/tree/main/%aé").is_err());
+}
+
+fn local_install(reference: &std::path::Path, skills_source: &std::path::Path) -> InstallResult {
+    let config = Config {
+        skills_source: skills_source.to_path_buf(),
+        commands_source: None,
+        destinations: Default::default(),
+        custom_tools: Default::default(),
+        default_host: None,
+    };
+    let options = InstallOptions {
+        reference: reference.to_string_lossy().to_string(),
+        existing: ExistingSkill::Prompt,
+        revision: None,
+    };
+    install_skill(&options, &config, &Scripted::new(), &Scripted::new()).unwrap()
+}
+
+#[test]
+fn test_install_skill_from_local_directory_records_a_file_url() {
+    let root = tempdir().unwrap();
+    let skill_dir = root.path().join("shared").join("release-notes");
+    write_skill(&skill_dir, "release-notes", "Draft release notes");
+    let skills_source = root.path().join("skills");
+
+    let result = local_install(&skill_dir, &skills_source);
+
+    assert_eq!(result.skill_slug, "release-notes");
+    assert!(
+        skills_source
+            .join("release-notes")
+            .join("SKILL.md")
+            .exists()
+    );
+    assert!(skill_dir.join("SKILL.md").exists());
+
+    let lockfile = Lockfile::load(&skills_source).unwrap();
+    let locked = lockfile.get("release-notes").unwrap();
+    assert!(locked.repo_url.starts_with("file://"));
+    assert!(locked.is_local());
+    assert_eq!(locked.commit, None);
+    assert_eq!(locked.source_hash.as_ref(), Some(&locked.content_hash));
+    assert_eq!(locked.subpath, PathBuf::new());
+
+    let error = install_skill(
+        &InstallOptions {
+            reference: skills_source
+                .join("release-notes")
+                .to_string_lossy()
+                .to_string(),
+            existing: ExistingSkill::Replace,
+            revision: None,
+        },
+        &Config {
+            skills_source: skills_source.clone(),
+            commands_source: None,
+            destinations: Default::default(),
+            custom_tools: Default::default(),
+            default_host: None,
+        },
+        &Scripted::new(),
+        &Scripted::new(),
+    )
+    .unwrap_err();
+    assert!(error.to_string().contains("inside skills_source"));
+}
+
+#[test]
+fn test_install_skill_from_tar_gz_and_zip_archives() {
+    let root = tempdir().unwrap();
+    let staging = root.path().join("build");
+    write_skill(&staging.join("packaged"), "packaged", "Built skill");
+
+    let tar_path = root.path().join("packaged.tar.gz");
+    let encoder = flate2::write::GzEncoder::new(
+        fs::File::create(&tar_path).unwrap(),
+        flate2::Compression::default(),
+    );
+    let mut tar = tar::Builder::new(encoder);
+    tar.append_dir_all("packaged", staging.join("packaged"))
+        .unwrap();
+    tar.into_inner().unwrap().finish().unwrap();
+
+    let zip_path = root.path().join("packaged.zip");
+    let mut zip = zip::ZipWriter::new(fs::File::create(&zip_path).unwrap());
+    zip.start_file("SKILL.md", zip::write::SimpleFileOptions::default())
+        .unwrap();
+    std::io::Write::write_all(
+        &mut zip,
+        b"---\nname: zipped\ndescription: From a zip\n---\n",
+    )
+    .unwrap();
+    zip.finish().unwrap();
+
+    let skills_source = root.path().join("skills");
+    let from_tar = local_install(&tar_path, &skills_source);
+    assert_eq!(from_tar.skill_slug, "packaged");
+    assert_eq!(from_tar.source_subpath, PathBuf::from("packaged"));
+
+    let from_zip = local_install(&zip_path, &skills_source);
+    assert_eq!(from_zip.skill_slug, "zipped");
+    assert!(skills_source.join("zipped").join("SKILL.md").exists());
+}
@@ -0,0 +1,124 @@
+use capsync::config::{Config, DestinationConfig, LinkMode};
+use capsync::error::CapsyncError;
+use capsync::list::inventory;
+use capsync::lockfile::{LockedSkill, Lockfile};
+use std::fs;
+use std::path::Path;
+use tempfile::tempdir;
+
+fn destination(home: &Path, name: &str, enabled: bool, commands: bool) -> DestinationConfig {
+    DestinationConfig {
+        enabled,
+        skills_path: home.join(name).join("skills"),
+        commands_path: commands.then(|| home.join(name).join("commands")),
+        link_mode: LinkMode::Symlink,
+    }
+}
+
+fn test_config(home: &Path) -> Config {
+    let mut config = Config::for_home(home);
+    config.skills_source = home.join("skills");
+    config.commands_source = Some(home.join("commands"));
+    config.destinations.clear();
+    config.destinations.insert(
+        "claude".to_string(),
+        destination(home, "claude", true, true),
+    );
+    config
+        .destinations
+        .insert("codex".to_string(), destination(home, "codex", true, false));
+    config.destinations.insert(
+        "cursor".to_string(),
+        destination(home, "cursor", false, true),
+    );
+
+    let demo = config.skills_source.join("demo");
+    fs::create_dir_all(&demo).unwrap();
+    fs::write(
+        demo.join("SKILL.md"),
+        "---\nname: Demo Skill\ndescription: \"Shows things\"\n---\n",
+    )
+    .unwrap();
+    let nested = config.skills_source.join("group").join("nested");
+    fs::create_dir_all(&nested).unwrap();
+    fs::write(nested.join("SKILL.md"), "# no frontmatter\n").unwrap();
+    let backup = config.skills_source.join(".old.backup.1");
+    fs::create_dir_all(&backup).unwrap();
+    fs::write(backup.join("SKILL.md"), "---\nname: old\n---\n").unwrap();
+
+    let commands = home.join("commands");
+    fs::create_dir_all(&commands).unwrap();
+    fs::write(
+        commands.join("deploy.md"),
+        "---\nname: deploy\ndescription: Ship it\n---\n",
+    )
+    .unwrap();
+
+    config
+}
+
+#[test]
+fn test_inventory_lists_skills_and_commands_with_tools() {
+    let home = tempdir().unwrap();
+    let config = test_config(home.path());
+    let mut lockfile = Lockfile::default();
+    lockfile.insert(
+        "demo",
+        LockedSkill {
+            reference: "owner/repo/demo".to_string(),
+            repo_url: "https://github.com/owner/repo.git".to_string(),
+            branch: Some("main".to_string()),
+            revision: None,
+            commit: Some("a".repeat(40)),
+            source_hash: None,
+            subpath: "skills/demo".into(),
+            content_hash: "sha1:0".to_string(),
+        },
+    );
+    lockfile.save(&config.skills_source).unwrap();
+
+    let inventory = inventory(&config, None).unwrap();
+
+    let slugs: Vec<_> = inventory.skills.iter().map(|s| s.slug.as_str()).collect();
+    assert_eq!(slugs, vec!["demo", "group/nested"]);
+
+    let demo = &inventory.skills[0];
+    assert_eq!(demo.name.as_deref(), Some("Demo Skill"));
+    assert_eq!(demo.description.as_deref(), Some("Shows things"));
+    assert_eq!(
+        demo.installed_from.as_ref().unwrap().reference,
+        "owner/repo/demo"
+    );
+    assert_eq!(demo.tools, vec!["claude", "codex"]);
+    assert!(inventory.skills[1].name.is_none());
+    assert!(inventory.skills[1].installed_from.is_none());
+
+    assert_eq!(inventory.commands.len(), 1);
+    assert_eq!(inventory.commands[0].slug, "deploy.md");
+    assert_eq!(
+        inventory.commands[0].description.as_deref(),
+        Some("Ship it")
+    );
+    assert_eq!(inventory.commands[0].tools, vec!["claude"]);
+
+    let json = serde_json::to_value(&inventory).unwrap();
+    assert_eq!(json["skills"][0]["installed_from"]["branch"], "main");
+}
+
+#[test]
+fn test_inventory_filters_by_tool() {
+    let home = tempdir().unwrap();
+    let config = test_config(home.path());
+
+    let codex = inventory(&config, Some("codex")).unwrap();
+    assert_eq!(codex.skills.len(), 2);
+    assert_eq!(codex.skills[0].tools, vec!["codex"]);
+    assert!(codex.commands.is_empty());
+
+    let cursor = inventory(&config, Some("cursor")).unwrap();
+    assert!(cursor.skills.is_empty());
+    assert!(cursor.commands.is_empty());
+
+    let error = inventory(&config, Some("nope")).unwrap_err();
+    assert!(matches!(error, CapsyncError::UnknownDestination(_)));
+}
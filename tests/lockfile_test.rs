@@ -0,0 +1,135 @@
+use capsync::install::{
+    ExistingSkill, ResolvedInstallRef, SkillSelector, install_skill_from_checkout, locked_skill,
+};
+use capsync::lockfile::{LOCKFILE_NAME, LockedSkill, Lockfile, hash_skill_tree};
+use capsync::ui::Scripted;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::tempdir;
+
+fn write_skill(skill_dir: &Path, name: &str) {
+    fs::create_dir_all(skill_dir).unwrap();
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        format!("---\nname: {}\ndescription: test\n---\n", name),
+    )
+    .unwrap();
+}
+
+fn commit_all(repo_dir: &Path) -> String {
+    let repository = git2::Repository::init(repo_dir).unwrap();
+    let mut index = repository.index().unwrap();
+    index
+        .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+        .unwrap();
+    let tree = repository.find_tree(index.write_tree().unwrap()).unwrap();
+    let signature = git2::Signature::now("test", "test@example.com").unwrap();
+    repository
+        .commit(Some("HEAD"), &signature, &signature, "init", &tree, &[])
+        .unwrap()
+        .to_string()
+}
+
+#[test]
+fn test_lockfile_round_trips_and_missing_file_is_empty() {
+    let skills_source = tempdir().unwrap();
+    assert_eq!(
+        Lockfile::load(skills_source.path()).unwrap(),
+        Lockfile::default()
+    );
+
+    let mut lockfile = Lockfile::default();
+    lockfile.insert(
+        "find-skills",
+        LockedSkill {
+            reference: "vercel-labs/skills/find-skills".to_string(),
+            repo_url: "https://github.com/vercel-labs/skills.git".to_string(),
+            branch: Some("main".to_string()),
+            revision: None,
+            commit: Some("0123456789abcdef0123456789abcdef01234567".to_string()),
+            source_hash: None,
+            subpath: PathBuf::from("skills/find-skills"),
+            content_hash: "sha1:abc".to_string(),
+        },
+    );
+    lockfile.save(skills_source.path()).unwrap();
+
+    let content = fs::read_to_string(skills_source.path().join(LOCKFILE_NAME)).unwrap();
+    assert!(content.contains("[skills.find-skills]"));
+    assert_eq!(Lockfile::load(skills_source.path()).unwrap(), lockfile);
+
+    lockfile.remove("find-skills");
+    assert!(lockfile.get("find-skills").is_none());
+}
+
+#[test]
+fn test_hash_skill_tree_depends_on_contents_and_paths() {
+    let first = tempdir().unwrap();
+    let second = tempdir().unwrap();
+    write_skill(first.path(), "demo");
+    write_skill(second.path(), "demo");
+    fs::create_dir_all(first.path().join("scripts")).unwrap();
+    fs::create_dir_all(second.path().join("scripts")).unwrap();
+    fs::write(first.path().join("scripts/run.sh"), "echo hi").unwrap();
+    fs::write(second.path().join("scripts/run.sh"), "echo hi").unwrap();
+
+    let hash = hash_skill_tree(first.path()).unwrap();
+    assert!(hash.starts_with("sha1:"));
+    assert_eq!(hash, hash_skill_tree(second.path()).unwrap());
+
+    fs::write(second.path().join("scripts/run.sh"), "echo bye").unwrap();
+    assert_ne!(hash, hash_skill_tree(second.path()).unwrap());
+
+    fs::write(second.path().join("scripts/run.sh"), "echo hi").unwrap();
+    fs::rename(
+        second.path().join("scripts/run.sh"),
+        second.path().join("scripts/go.sh"),
+    )
+    .unwrap();
+    assert_ne!(hash, hash_skill_tree(second.path()).unwrap());
+}
+
+#[test]
+fn test_locked_skill_records_checkout_provenance() {
+    let checkout_dir = tempdir().unwrap();
+    let target_dir = tempdir().unwrap();
+    write_skill(
+        &checkout_dir.path().join("skills").join("find-skills"),
+        "Find Skills",
+    );
+    let commit = commit_all(checkout_dir.path());
+
+    let resolved = ResolvedInstallRef {
+        repo_url: "https://github.com/vercel-labs/skills.git".to_string(),
+        branch: None,
+        revision: None,
+        selector: SkillSelector::Slug("find-skills".to_string()),
+    };
+    let result = install_skill_from_checkout(
+        checkout_dir.path(),
+        &resolved,
+        target_dir.path(),
+        ExistingSkill::Prompt,
+        &Scripted::new(),
+    )
+    .unwrap();
+
+    let locked = locked_skill(
+        " vercel-labs/skills/find-skills ",
+        &resolved,
+        checkout_dir.path(),
+        &result,
+    )
+    .unwrap();
+
+    assert_eq!(locked.reference, "vercel-labs/skills/find-skills");
+    assert_eq!(locked.repo_url, resolved.repo_url);
+    assert!(locked.branch.is_some());
+    assert_eq!(locked.commit, Some(commit));
+    assert!(!locked.is_local());
+    assert_eq!(locked.subpath, PathBuf::from("skills/find-skills"));
+    assert_eq!(
+        locked.content_hash,
+        hash_skill_tree(&target_dir.path().join("find-skills")).unwrap()
+    );
+}
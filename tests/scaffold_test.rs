@@ -0,0 +1,152 @@
+use capsync::config::Config;
+use capsync::error::CapsyncError;
+use capsync::scaffold::{NewOptions, create, template_names};
+use capsync::sync::SourceKind;
+use capsync::validate::{Frontmatter, validate_skill};
+use std::fs;
+use std::path::Path;
+use tempfile::tempdir;
+
+fn test_config(home: &Path) -> Config {
+    let mut config = Config::for_home(home);
+    config.skills_source = home.join("skills");
+    config.commands_source = Some(home.join("commands"));
+    fs::create_dir_all(&config.skills_source).unwrap();
+    fs::create_dir_all(home.join("commands")).unwrap();
+    config
+}
+
+fn options(kind: SourceKind, name: &str) -> NewOptions {
+    NewOptions {
+        kind,
+        name: name.to_string(),
+        description: None,
+        template: None,
+    }
+}
+
+#[test]
+fn test_new_skill_from_builtin_template_is_valid() {
+    let home = tempdir().unwrap();
+    let config = test_config(home.path());
+    let templates = home.path().join("templates");
+
+    let mut new_skill = options(SourceKind::Skills, "Release Notes!");
+    new_skill.description = Some("Use when: drafting release notes".to_string());
+    let result = create(&new_skill, &config, &templates).unwrap();
+
+    assert_eq!(result.slug, "release-notes");
+    assert_eq!(result.template, "default");
+    assert_eq!(result.path, config.skills_source.join("release-notes"));
+    assert!(validate_skill(&result.path).unwrap().is_empty());
+
+    let content = fs::read_to_string(result.path.join("SKILL.md")).unwrap();
+    let frontmatter = Frontmatter::parse(&content).unwrap().unwrap();
+    assert_eq!(frontmatter.get_str("name"), Some("release-notes"));
+    assert_eq!(
+        frontmatter.get_str("description"),
+        Some("Use when: drafting release notes")
+    );
+    assert!(content.contains("# Release Notes!\n"));
+
+    let error = create(&new_skill, &config, &templates).unwrap_err();
+    assert!(error.to_string().contains("already exists"));
+}
+
+#[test]
+fn test_new_skill_quotes_names_yaml_would_read_as_other_types() {
+    let home = tempdir().unwrap();
+    let config = test_config(home.path());
+    let templates = home.path().join("templates");
+
+    for name in ["123", "1-0", "null", "true"] {
+        let mut new_skill = options(SourceKind::Skills, name);
+        new_skill.description = Some("1.0".to_string());
+        let result = create(&new_skill, &config, &templates).unwrap();
+
+        assert!(validate_skill(&result.path).unwrap().is_empty(), "{name}");
+        let content = fs::read_to_string(result.path.join("SKILL.md")).unwrap();
+        let frontmatter = Frontmatter::parse(&content).unwrap().unwrap();
+        assert_eq!(frontmatter.get_str("name"), Some(name));
+        assert_eq!(frontmatter.get_str("description"), Some("1.0"));
+    }
+}
+
+#[test]
+fn test_new_skill_from_user_template_copies_every_file() {
+    let home = tempdir().unwrap();
+    let config = test_config(home.path());
+    let templates = home.path().join("templates");
+    let template_dir = templates.join("skills").join("scripted");
+    fs::create_dir_all(template_dir.join("scripts")).unwrap();
+    fs::write(
+        template_dir.join("SKILL.md"),
+        "---\nname: {{name}}\ndescription: {{description}}\n---\n\nRun [it](scripts/run.sh).\n",
+    )
+    .unwrap();
+    fs::write(
+        template_dir.join("scripts").join("run.sh"),
+        "echo {{title}}\n",
+    )
+    .unwrap();
+    let broken_dir = templates.join("skills").join("broken");
+    fs::create_dir_all(&broken_dir).unwrap();
+    fs::write(broken_dir.join("SKILL.md"), "# no frontmatter\n").unwrap();
+
+    assert_eq!(
+        template_names(SourceKind::Skills, &templates).unwrap(),
+        vec!["broken", "default", "scripted"]
+    );
+
+    let mut new_skill = options(SourceKind::Skills, "lint-fix");
+    new_skill.template = Some("scripted".to_string());
+    let result = create(&new_skill, &config, &templates).unwrap();
+    assert_eq!(
+        fs::read_to_string(result.path.join("scripts").join("run.sh")).unwrap(),
+        "echo Lint Fix\n"
+    );
+    assert!(validate_skill(&result.path).unwrap().is_empty());
+
+    new_skill.name = "other".to_string();
+    new_skill.template = Some("broken".to_string());
+    let error = create(&new_skill, &config, &templates).unwrap_err();
+    assert!(matches!(error, CapsyncError::InvalidSkill { .. }));
+    assert!(!config.skills_source.join("other").exists());
+
+    new_skill.template = Some("missing".to_string());
+    let error = create(&new_skill, &config, &templates).unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .contains("Available: broken, default, scripted")
+    );
+}
+
+#[test]
+fn test_new_command_writes_markdown_into_commands_source() {
+    let home = tempdir().unwrap();
+    let mut config = test_config(home.path());
+    let templates = home.path().join("templates");
+
+    let result = create(
+        &options(SourceKind::Commands, "Deploy App"),
+        &config,
+        &templates,
+    )
+    .unwrap();
+    assert_eq!(
+        result.path,
+        home.path().join("commands").join("deploy-app.md")
+    );
+    let content = fs::read_to_string(&result.path).unwrap();
+    let frontmatter = Frontmatter::parse(&content).unwrap().unwrap();
+    assert_eq!(frontmatter.get_str("name"), Some("deploy-app"));
+    assert_eq!(
+        frontmatter.get_str("description"),
+        Some("Describe what Deploy App does")
+    );
+
+    config.commands_source = None;
+    let error = create(&options(SourceKind::Commands, "other"), &config, &templates).unwrap_err();
+    assert!(matches!(error, CapsyncError::ConfigInvalid(_)));
+}
@@ -0,0 +1,160 @@
+use capsync::config::{Config, LinkMode};
+use capsync::status::{LinkState, config_report, status_report};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn test_config(home: &Path, skills_dir: &Path) -> Config {
+    let mut config = Config::for_home(home);
+    config.skills_source = skills_dir.to_path_buf();
+    config
+        .destinations
+        .retain(|name, _| ["claude", "cursor", "opencode", "codex"].contains(&name.as_str()));
+    for dest in config.destinations.values_mut() {
+        dest.enabled = true;
+    }
+    config
+}
+
+#[cfg(unix)]
+#[test]
+fn test_status_report_classifies_destinations() {
+    let temp_dir = TempDir::new().unwrap();
+    let home = temp_dir.path().join("home");
+    let skills_dir = temp_dir.path().join("skills");
+    fs::create_dir_all(&skills_dir).unwrap();
+
+    let config = test_config(&home, &skills_dir);
+    let claude = &config.destinations["claude"].skills_path;
+    let cursor = &config.destinations["cursor"].skills_path;
+    let opencode = &config.destinations["opencode"].skills_path;
+    fs::create_dir_all(claude.parent().unwrap()).unwrap();
+    std::os::unix::fs::symlink(&skills_dir, claude).unwrap();
+    fs::create_dir_all(cursor).unwrap();
+    fs::create_dir_all(opencode.parent().unwrap()).unwrap();
+    std::os::unix::fs::symlink(temp_dir.path().join("gone"), opencode).unwrap();
+
+    let report = status_report(&config, &temp_dir.path().join("config.toml"));
+    let names: Vec<_> = report
+        .destinations
+        .iter()
+        .map(|d| d.name.as_str())
+        .collect();
+    assert_eq!(names, ["claude", "codex", "cursor", "opencode"]);
+
+    assert!(report.skills_source.exists);
+    assert!(matches!(
+        report.destinations[0].skills.state,
+        LinkState::Linked { .. }
+    ));
+    assert!(matches!(
+        report.destinations[1].skills.state,
+        LinkState::Missing
+    ));
+    assert!(matches!(
+        report.destinations[2].skills.state,
+        LinkState::ForeignDirectory
+    ));
+    assert!(matches!(
+        report.destinations[3].skills.state,
+        LinkState::Broken { .. }
+    ));
+}
+
+#[test]
+fn test_status_report_serializes_state_tags() {
+    let temp_dir = TempDir::new().unwrap();
+    let home = temp_dir.path().join("home");
+    let skills_dir = temp_dir.path().join("skills");
+    fs::create_dir_all(skills_dir.join("my-skill")).unwrap();
+
+    let mut config = test_config(&home, &skills_dir);
+    config.destinations.retain(|name, _| name == "claude");
+    let claude = config.destinations.get_mut("claude").unwrap();
+    claude.link_mode = LinkMode::PerSkill;
+    fs::create_dir_all(&claude.skills_path).unwrap();
+
+    let report = status_report(&config, &temp_dir.path().join("config.toml"));
+    let json: serde_json::Value = serde_json::to_value(&report).unwrap();
+    let destination = &json["destinations"][0];
+
+    assert_eq!(destination["name"], "claude");
+    assert_eq!(destination["link_mode"], "per-skill");
+    assert_eq!(destination["skills"]["state"], "per-skill");
+    assert_eq!(destination["skills"]["missing"][0], "my-skill");
+    assert_eq!(destination["commands"]["state"], "no-source");
+    assert_eq!(json["commands_source"], serde_json::Value::Null);
+}
+
+#[test]
+fn test_config_report_lists_destinations_sorted() {
+    let temp_dir = TempDir::new().unwrap();
+    let config = test_config(&temp_dir.path().join("home"), temp_dir.path());
+
+    let report = config_report(&config, &temp_dir.path().join("config.toml"));
+    let json = serde_json::to_value(&report).unwrap();
+
+    assert_eq!(json["destinations"][0]["name"], "claude");
+    assert_eq!(json["destinations"][0]["enabled"], true);
+    assert_eq!(json["destinations"].as_array().unwrap().len(), 4);
+    assert!(json["custom_tools"].as_object().unwrap().is_empty());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_status_report_detects_links_to_another_source() {
+    let temp_dir = TempDir::new().unwrap();
+    let home = temp_dir.path().join("home");
+    let skills_dir = temp_dir.path().join("skills");
+    let old_source = temp_dir.path().join("old-skills");
+    fs::create_dir_all(&skills_dir).unwrap();
+    fs::create_dir_all(&old_source).unwrap();
+
+    let mut config = test_config(&home, &skills_dir);
+    config.destinations.get_mut("codex").unwrap().enabled = false;
+    let claude = config.destinations["claude"].skills_path.clone();
+    let cursor = config.destinations["cursor"].skills_path.clone();
+    let opencode = config.destinations["opencode"].skills_path.clone();
+    for path in [&claude, &cursor, &opencode] {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+    }
+    std::os::unix::fs::symlink(&old_source, &claude).unwrap();
+    std::os::unix::fs::symlink(&skills_dir, &cursor).unwrap();
+    // A relative link is resolved against its own directory.
+    let depth = opencode
+        .parent()
+        .unwrap()
+        .strip_prefix(temp_dir.path())
+        .unwrap()
+        .components()
+        .count();
+    let relative = "../".repeat(depth) + "skills";
+    std::os::unix::fs::symlink(&relative, &opencode).unwrap();
+    assert!(opencode.parent().unwrap().join(&relative).exists());
+
+    let report = status_report(&config, &temp_dir.path().join("config.toml"));
+    let state = |name: &str| {
+        report
+            .destinations
+            .iter()
+            .find(|dest| dest.name == name)
+            .unwrap()
+    };
+
+    assert!(matches!(
+        state("claude").skills.state,
+        LinkState::LinkedElsewhere { .. }
+    ));
+    assert!(!state("claude").in_sync);
+    assert!(state("cursor").in_sync);
+    assert!(matches!(
+        state("opencode").skills.state,
+        LinkState::Linked { .. }
+    ));
+    assert!(!state("codex").in_sync);
+
+    // The disabled, missing codex destination does not count.
+    let out_of_sync: Vec<_> = report.out_of_sync().map(|dest| &dest.name).collect();
+    assert_eq!(out_of_sync, ["claude"]);
+    assert!(!report.in_sync);
+}
@@ -1,14 +1,20 @@
-use capsync::config::{Config, DestinationConfig};
-use capsync::sync::SyncManager;
+use capsync::config::{Config, DestinationConfig, LinkMode};
+use capsync::sync::{AdoptStrategy, MIRROR_MANIFEST, PlannedAction, SourceKind, SyncManager};
+use capsync::ui::Scripted;
 use std::fs;
+use std::path::Path;
+use std::sync::LazyLock;
 use tempfile::TempDir;
 
+/// Stands in for the home directory so tests never touch the real one.
+static TEST_HOME: LazyLock<TempDir> = LazyLock::new(|| TempDir::new().unwrap());
+
 fn create_test_config(
     skills_source: Option<&str>,
     commands_source: Option<&str>,
     tools: &[(&str, bool)],
 ) -> Config {
-    let mut config = Config::default();
+    let mut config = Config::for_home(TEST_HOME.path());
 
     if let Some(skills) = skills_source {
         config.skills_source = std::path::PathBuf::from(skills);
@@ -160,6 +166,7 @@ fn test_sync_all_with_both_skills_and_commands() {
             enabled: true,
             skills_path: dest_skills.clone(),
             commands_path: Some(dest_commands.clone()),
+            link_mode: LinkMode::Symlink,
         },
     );
 
@@ -203,3 +210,536 @@ fn test_sync_commands_with_disabled_tool() {
     assert!(result.successful.is_empty());
     assert!(result.errors.is_empty());
 }
+
+fn per_skill_config(skills_dir: &Path, dest: &Path) -> Config {
+    let mut config = create_test_config(Some(skills_dir.to_str().unwrap()), None, &[]);
+    config.destinations.insert(
+        "claude".to_string(),
+        DestinationConfig {
+            enabled: true,
+            skills_path: dest.to_path_buf(),
+            commands_path: None,
+            link_mode: LinkMode::PerSkill,
+        },
+    );
+    config
+}
+
+#[cfg(unix)]
+#[test]
+fn test_sync_per_skill_keeps_existing_tool_skills() {
+    let temp_dir = TempDir::new().unwrap();
+    let skills_dir = temp_dir.path().join("skills");
+    let dest = temp_dir.path().join("claude-skills");
+    fs::create_dir_all(skills_dir.join("my-skill")).unwrap();
+    fs::create_dir_all(dest.join("native-skill")).unwrap();
+
+    let config = per_skill_config(&skills_dir, &dest);
+    let result = SyncManager::sync_skills(&config).unwrap();
+
+    assert_eq!(result.successful, vec!["claude".to_string()]);
+    assert!(!dest.is_symlink());
+    assert!(dest.join("native-skill").is_dir());
+    assert_eq!(
+        fs::read_link(dest.join("my-skill")).unwrap(),
+        skills_dir.join("my-skill")
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_sync_per_skill_reports_conflicts_and_prunes_stale_links() {
+    let temp_dir = TempDir::new().unwrap();
+    let skills_dir = temp_dir.path().join("skills");
+    let dest = temp_dir.path().join("claude-skills");
+    fs::create_dir_all(skills_dir.join("shared")).unwrap();
+    fs::create_dir_all(skills_dir.join("old-skill")).unwrap();
+    fs::create_dir_all(dest.join("shared")).unwrap();
+
+    let config = per_skill_config(&skills_dir, &dest);
+    SyncManager::sync_skills(&config).unwrap();
+    assert!(dest.join("old-skill").is_symlink());
+
+    fs::remove_dir_all(skills_dir.join("old-skill")).unwrap();
+    let result = SyncManager::sync_skills(&config).unwrap();
+
+    assert_eq!(result.errors.len(), 1);
+    assert!(result.errors[0].1.contains("shared"));
+    assert!(!dest.join("old-skill").is_symlink());
+    assert!(!dest.join("shared").is_symlink());
+
+    let status = SyncManager::per_skill_status(&skills_dir, &dest, SourceKind::Skills).unwrap();
+    assert_eq!(status.conflicts, vec!["shared".to_string()]);
+    assert!(status.linked.is_empty());
+    assert!(status.stale.is_empty());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_remove_per_skill_only_removes_managed_links() {
+    let temp_dir = TempDir::new().unwrap();
+    let skills_dir = temp_dir.path().join("skills");
+    let dest = temp_dir.path().join("claude-skills");
+    fs::create_dir_all(skills_dir.join("my-skill")).unwrap();
+    fs::create_dir_all(dest.join("native-skill")).unwrap();
+
+    let config = per_skill_config(&skills_dir, &dest);
+    SyncManager::sync_skills(&config).unwrap();
+    SyncManager::remove("claude", &config, &Scripted::new()).unwrap();
+
+    assert!(dest.is_dir());
+    assert!(dest.join("native-skill").is_dir());
+    assert!(!dest.join("my-skill").exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_sync_per_skill_replaces_whole_directory_symlink() {
+    let temp_dir = TempDir::new().unwrap();
+    let skills_dir = temp_dir.path().join("skills");
+    let dest = temp_dir.path().join("claude-skills");
+    fs::create_dir_all(skills_dir.join("my-skill")).unwrap();
+    std::os::unix::fs::symlink(&skills_dir, &dest).unwrap();
+
+    let config = per_skill_config(&skills_dir, &dest);
+    SyncManager::sync_skills(&config).unwrap();
+
+    assert!(!dest.is_symlink());
+    assert!(dest.join("my-skill").is_symlink());
+}
+
+fn mirror_config(skills_dir: &Path, dest: &Path, link_mode: LinkMode) -> Config {
+    let mut config = per_skill_config(skills_dir, dest);
+    config.destinations.get_mut("claude").unwrap().link_mode = link_mode;
+    config
+}
+
+#[test]
+fn test_sync_copy_mirrors_updates_and_prunes_files() {
+    let temp_dir = TempDir::new().unwrap();
+    let skills_dir = temp_dir.path().join("skills");
+    let dest = temp_dir.path().join("claude-skills");
+    fs::create_dir_all(skills_dir.join("my-skill/docs")).unwrap();
+    fs::write(skills_dir.join("my-skill/SKILL.md"), "v1").unwrap();
+    fs::write(skills_dir.join("my-skill/docs/extra.md"), "extra").unwrap();
+    fs::create_dir_all(dest.join("native-skill")).unwrap();
+
+    let config = mirror_config(&skills_dir, &dest, LinkMode::Copy);
+    let result = SyncManager::sync_skills(&config).unwrap();
+
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    assert!(!dest.join("my-skill").is_symlink());
+    assert_eq!(
+        fs::read_to_string(dest.join("my-skill/SKILL.md")).unwrap(),
+        "v1"
+    );
+
+    fs::write(skills_dir.join("my-skill/SKILL.md"), "v2").unwrap();
+    fs::remove_dir_all(skills_dir.join("my-skill/docs")).unwrap();
+
+    let status =
+        SyncManager::mirror_status(&skills_dir, &dest, SourceKind::Skills, LinkMode::Copy).unwrap();
+    assert!(!status.is_in_sync());
+    assert_eq!(status.changed, vec![Path::new("my-skill/SKILL.md")]);
+    assert_eq!(status.stale, vec![Path::new("my-skill/docs/extra.md")]);
+
+    SyncManager::sync_skills(&config).unwrap();
+
+    assert_eq!(
+        fs::read_to_string(dest.join("my-skill/SKILL.md")).unwrap(),
+        "v2"
+    );
+    assert!(!dest.join("my-skill/docs").exists());
+    assert!(dest.join("native-skill").is_dir());
+    assert!(
+        SyncManager::mirror_status(&skills_dir, &dest, SourceKind::Skills, LinkMode::Copy)
+            .unwrap()
+            .is_in_sync()
+    );
+}
+
+#[test]
+fn test_sync_copy_refuses_to_overwrite_unmanaged_entries() {
+    let temp_dir = TempDir::new().unwrap();
+    let skills_dir = temp_dir.path().join("skills");
+    let dest = temp_dir.path().join("claude-skills");
+    fs::create_dir_all(skills_dir.join("shared")).unwrap();
+    fs::write(skills_dir.join("shared/SKILL.md"), "ours").unwrap();
+    fs::create_dir_all(dest.join("shared")).unwrap();
+    fs::write(dest.join("shared/SKILL.md"), "theirs").unwrap();
+
+    let config = mirror_config(&skills_dir, &dest, LinkMode::Copy);
+    let result = SyncManager::sync_skills(&config).unwrap();
+
+    assert_eq!(result.errors.len(), 1);
+    assert_eq!(
+        fs::read_to_string(dest.join("shared/SKILL.md")).unwrap(),
+        "theirs"
+    );
+}
+
+#[test]
+fn test_mirror_manifest_entries_outside_the_destination_are_refused() {
+    let temp_dir = TempDir::new().unwrap();
+    let skills_dir = temp_dir.path().join("skills");
+    let dest = temp_dir.path().join("claude-skills");
+    fs::create_dir_all(skills_dir.join("my-skill")).unwrap();
+    fs::write(skills_dir.join("my-skill/SKILL.md"), "v1").unwrap();
+
+    let config = mirror_config(&skills_dir, &dest, LinkMode::Copy);
+    SyncManager::sync_skills(&config).unwrap();
+
+    let outside = temp_dir.path().join("outside.txt");
+    fs::write(&outside, "keep me").unwrap();
+    let manifest = dest.join(MIRROR_MANIFEST);
+    for entry in ["../outside.txt".to_string(), outside.display().to_string()] {
+        fs::write(&manifest, format!("my-skill/SKILL.md\n{}\n", entry)).unwrap();
+
+        let sync = SyncManager::sync_skills(&config);
+        assert!(sync.is_err() || !sync.unwrap().errors.is_empty(), "{entry}");
+        assert!(SyncManager::remove("claude", &config, &Scripted::new()).is_err());
+        assert_eq!(fs::read_to_string(&outside).unwrap(), "keep me");
+        assert!(dest.join("my-skill/SKILL.md").exists());
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn test_sync_hardlink_shares_inodes_and_remove_keeps_native_files() {
+    use std::os::unix::fs::MetadataExt;
+
+    let temp_dir = TempDir::new().unwrap();
+    let skills_dir = temp_dir.path().join("skills");
+    let dest = temp_dir.path().join("claude-skills");
+    fs::create_dir_all(skills_dir.join("my-skill")).unwrap();
+    fs::write(skills_dir.join("my-skill/SKILL.md"), "v1").unwrap();
+    fs::create_dir_all(dest.join("native-skill")).unwrap();
+
+    let config = mirror_config(&skills_dir, &dest, LinkMode::Hardlink);
+    SyncManager::sync_skills(&config).unwrap();
+
+    assert_eq!(
+        fs::metadata(skills_dir.join("my-skill/SKILL.md"))
+            .unwrap()
+            .ino(),
+        fs::metadata(dest.join("my-skill/SKILL.md")).unwrap().ino()
+    );
+
+    SyncManager::remove("claude", &config, &Scripted::new()).unwrap();
+
+    assert!(!dest.join("my-skill").exists());
+    assert!(dest.join("native-skill").is_dir());
+    assert!(skills_dir.join("my-skill/SKILL.md").exists());
+}
+
+#[test]
+fn test_plan_skills_does_not_touch_filesystem() {
+    let temp_dir = TempDir::new().unwrap();
+    let skills_dir = temp_dir.path().join("skills");
+    let claude_dest = temp_dir.path().join("claude-skills");
+    fs::create_dir_all(&skills_dir).unwrap();
+    fs::create_dir_all(&claude_dest).unwrap();
+
+    let mut config = create_test_config(
+        Some(skills_dir.to_str().unwrap()),
+        None,
+        &[("claude", true)],
+    );
+    config.destinations.get_mut("claude").unwrap().skills_path = claude_dest.clone();
+
+    let plan = SyncManager::plan_skills(&config).unwrap();
+
+    assert_eq!(plan.entries.len(), 1);
+    assert_eq!(
+        plan.entries[0].actions,
+        vec![
+            PlannedAction::RemoveEmptyDirectory {
+                path: claude_dest.clone()
+            },
+            PlannedAction::CreateLink {
+                source: skills_dir.clone(),
+                dest: claude_dest.clone()
+            },
+        ]
+    );
+    assert!(plan.has_changes());
+    assert!(claude_dest.is_dir());
+    assert!(!claude_dest.is_symlink());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_plan_skills_skips_correct_links_and_replaces_others() {
+    let temp_dir = TempDir::new().unwrap();
+    let skills_dir = temp_dir.path().join("skills");
+    let old_dir = temp_dir.path().join("old-skills");
+    let claude_dest = temp_dir.path().join("claude-skills");
+    let opencode_dest = temp_dir.path().join("opencode-skills");
+    fs::create_dir_all(&skills_dir).unwrap();
+    fs::create_dir_all(&old_dir).unwrap();
+    std::os::unix::fs::symlink(&skills_dir, &claude_dest).unwrap();
+    std::os::unix::fs::symlink(&old_dir, &opencode_dest).unwrap();
+
+    let mut config = create_test_config(
+        Some(skills_dir.to_str().unwrap()),
+        None,
+        &[("claude", true), ("opencode", true)],
+    );
+    config.destinations.get_mut("claude").unwrap().skills_path = claude_dest.clone();
+    config.destinations.get_mut("opencode").unwrap().skills_path = opencode_dest.clone();
+
+    let plan = SyncManager::plan_skills(&config).unwrap();
+
+    assert_eq!(plan.entries[0].name, "claude");
+    assert!(matches!(
+        plan.entries[0].actions.as_slice(),
+        [PlannedAction::Skip { .. }]
+    ));
+    assert_eq!(
+        plan.entries[1].actions,
+        vec![PlannedAction::ReplaceLink {
+            dest: opencode_dest.clone(),
+            old_target: old_dir,
+            source: skills_dir.clone(),
+        }]
+    );
+
+    let result = SyncManager::apply(&plan);
+    assert_eq!(result.successful.len(), 2);
+    assert_eq!(fs::read_link(&opencode_dest).unwrap(), skills_dir);
+}
+
+#[test]
+fn test_plan_remove_reports_real_directory_without_removing_it() {
+    let temp_dir = TempDir::new().unwrap();
+    let claude_dest = temp_dir.path().join("claude-skills");
+    fs::create_dir_all(&claude_dest).unwrap();
+
+    let mut config = create_test_config(Some("/tmp/skills"), None, &[("claude", true)]);
+    config.destinations.get_mut("claude").unwrap().skills_path = claude_dest.clone();
+
+    let plan = SyncManager::plan_remove("claude", &config).unwrap();
+
+    assert!(plan.entries[0].actions.is_empty());
+    assert!(
+        plan.entries[0]
+            .error
+            .as_ref()
+            .unwrap()
+            .contains("exists but is not a symlink")
+    );
+    assert!(SyncManager::remove("claude", &config, &Scripted::new()).is_err());
+    assert!(claude_dest.is_dir());
+}
+
+#[test]
+fn test_plan_remove_unknown_destination_fails() {
+    let config = create_test_config(Some("/tmp/skills"), None, &[]);
+
+    let error = SyncManager::plan_remove("not-a-tool", &config).unwrap_err();
+
+    assert!(error.to_string().contains("Unknown destination"));
+}
+
+fn adopt_config(skills_dir: &Path, dest: &Path) -> Config {
+    let mut config = create_test_config(Some(skills_dir.to_str().unwrap()), None, &[]);
+    config.destinations.retain(|name, _| name == "claude");
+    let claude = config.destinations.get_mut("claude").unwrap();
+    claude.enabled = true;
+    claude.skills_path = dest.to_path_buf();
+    claude.commands_path = None;
+    config
+}
+
+#[test]
+fn test_sync_refuses_to_replace_real_directory_with_content() {
+    let temp_dir = TempDir::new().unwrap();
+    let skills_dir = temp_dir.path().join("skills");
+    let dest = temp_dir.path().join("claude-skills");
+    fs::create_dir_all(&skills_dir).unwrap();
+    fs::create_dir_all(dest.join("native-skill")).unwrap();
+    fs::write(dest.join("native-skill/SKILL.md"), "native").unwrap();
+
+    let config = adopt_config(&skills_dir, &dest);
+    let result = SyncManager::sync_skills(&config).unwrap();
+
+    assert!(result.successful.is_empty());
+    assert_eq!(result.errors.len(), 1);
+    assert!(result.errors[0].1.contains("capsync adopt claude"));
+    assert!(!dest.is_symlink());
+    assert!(dest.join("native-skill/SKILL.md").exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_adopt_moves_entries_into_source_and_renames_clashes() {
+    let temp_dir = TempDir::new().unwrap();
+    let skills_dir = temp_dir.path().join("skills");
+    let dest = temp_dir.path().join("claude-skills");
+    fs::create_dir_all(skills_dir.join("shared")).unwrap();
+    fs::write(skills_dir.join("shared/SKILL.md"), "mine").unwrap();
+    fs::create_dir_all(dest.join("shared")).unwrap();
+    fs::write(dest.join("shared/SKILL.md"), "theirs").unwrap();
+    fs::create_dir_all(dest.join("native-skill")).unwrap();
+    fs::write(dest.join("native-skill/SKILL.md"), "native").unwrap();
+
+    let config = adopt_config(&skills_dir, &dest);
+    let plan = SyncManager::plan_adopt("claude", &config, AdoptStrategy::MoveToSource).unwrap();
+    assert!(dest.join("shared").is_dir());
+
+    let result = SyncManager::apply(&plan);
+    assert!(result.errors.is_empty());
+    assert!(!dest.exists());
+    assert_eq!(
+        fs::read_to_string(skills_dir.join("shared/SKILL.md")).unwrap(),
+        "mine"
+    );
+    assert_eq!(
+        fs::read_to_string(skills_dir.join("shared-claude/SKILL.md")).unwrap(),
+        "theirs"
+    );
+    assert!(skills_dir.join("native-skill/SKILL.md").exists());
+
+    let result = SyncManager::sync_skills(&config).unwrap();
+    assert!(result.errors.is_empty());
+    assert_eq!(fs::read_link(&dest).unwrap(), skills_dir);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_adopt_drops_links_capsync_created() {
+    let temp_dir = TempDir::new().unwrap();
+    let skills_dir = temp_dir.path().join("skills");
+    let dest = temp_dir.path().join("claude-skills");
+    fs::create_dir_all(skills_dir.join("my-skill")).unwrap();
+    fs::create_dir_all(&dest).unwrap();
+    std::os::unix::fs::symlink(skills_dir.join("my-skill"), dest.join("my-skill")).unwrap();
+
+    let config = adopt_config(&skills_dir, &dest);
+    let plan = SyncManager::plan_adopt("claude", &config, AdoptStrategy::MoveToSource).unwrap();
+
+    assert_eq!(
+        plan.entries[0].actions,
+        vec![
+            PlannedAction::RemoveLink {
+                path: dest.join("my-skill")
+            },
+            PlannedAction::RemoveEmptyDirectory { path: dest.clone() },
+        ]
+    );
+}
+
+#[test]
+fn test_adopt_backup_moves_destination_aside() {
+    let temp_dir = TempDir::new().unwrap();
+    let skills_dir = temp_dir.path().join("skills");
+    let dest = temp_dir.path().join("claude-skills");
+    fs::create_dir_all(&skills_dir).unwrap();
+    fs::create_dir_all(dest.join("native-skill")).unwrap();
+
+    let config = adopt_config(&skills_dir, &dest);
+    let plan = SyncManager::plan_adopt("claude", &config, AdoptStrategy::Backup).unwrap();
+    let result = SyncManager::apply(&plan);
+
+    assert!(result.errors.is_empty());
+    assert!(!dest.exists());
+    let backups: Vec<_> = fs::read_dir(temp_dir.path())
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .filter(|name| name.starts_with("claude-skills.backup."))
+        .collect();
+    assert_eq!(backups.len(), 1);
+    assert!(
+        temp_dir
+            .path()
+            .join(&backups[0])
+            .join("native-skill")
+            .is_dir()
+    );
+    assert!(!skills_dir.join("native-skill").exists());
+}
+
+#[test]
+fn test_plan_skills_syncs_shared_path_once() {
+    let temp_dir = TempDir::new().unwrap();
+    let skills_dir = temp_dir.path().join("skills");
+    let shared_dest = temp_dir.path().join("agents-skills");
+    fs::create_dir_all(&skills_dir).unwrap();
+
+    let mut config = create_test_config(Some(skills_dir.to_str().unwrap()), None, &[]);
+    config
+        .destinations
+        .retain(|name, _| ["amp", "kimi-cli", "cursor"].contains(&name.as_str()));
+    for (name, dest_config) in config.destinations.iter_mut() {
+        dest_config.enabled = true;
+        dest_config.skills_path = if name == "cursor" {
+            temp_dir.path().join("cursor-skills")
+        } else {
+            shared_dest.clone()
+        };
+    }
+
+    let plan = SyncManager::plan_skills(&config).unwrap();
+
+    assert_eq!(plan.entries.len(), 2);
+    assert_eq!(plan.entries[0].name, "amp, kimi-cli");
+    assert_eq!(plan.entries[0].path, shared_dest);
+    assert_eq!(plan.entries[1].name, "cursor");
+
+    let result = SyncManager::apply(&plan);
+    assert!(result.errors.is_empty());
+    assert!(shared_dest.is_symlink());
+}
+
+#[test]
+fn test_plan_skills_reports_shared_path_with_different_link_modes() {
+    let temp_dir = TempDir::new().unwrap();
+    let skills_dir = temp_dir.path().join("skills");
+    let shared_dest = temp_dir.path().join("agents-skills");
+    fs::create_dir_all(&skills_dir).unwrap();
+
+    let mut config = create_test_config(Some(skills_dir.to_str().unwrap()), None, &[]);
+    config
+        .destinations
+        .retain(|name, _| ["amp", "replit"].contains(&name.as_str()));
+    for (name, dest_config) in config.destinations.iter_mut() {
+        dest_config.enabled = true;
+        dest_config.skills_path = shared_dest.clone();
+        if name == "replit" {
+            dest_config.link_mode = LinkMode::Copy;
+        }
+    }
+
+    let plan = SyncManager::plan_skills(&config).unwrap();
+
+    assert_eq!(plan.entries.len(), 1);
+    assert!(plan.entries[0].actions.is_empty());
+    assert!(
+        plan.entries[0]
+            .error
+            .as_ref()
+            .unwrap()
+            .contains("different link modes")
+    );
+}
+
+#[test]
+fn test_tools_sharing_paths_only_lists_enabled_tools() {
+    let temp_dir = TempDir::new().unwrap();
+    let shared_dest = temp_dir.path().join("agents-skills");
+
+    let mut config = create_test_config(Some("/tmp/skills"), None, &[]);
+    config
+        .destinations
+        .retain(|name, _| ["amp", "kimi-cli", "replit"].contains(&name.as_str()));
+    for (name, dest_config) in config.destinations.iter_mut() {
+        dest_config.enabled = name != "replit";
+        dest_config.skills_path = shared_dest.clone();
+    }
+
+    assert_eq!(
+        SyncManager::tools_sharing_paths("amp", &config),
+        vec!["kimi-cli".to_string()]
+    );
+    assert!(SyncManager::tools_sharing_paths("unknown", &config).is_empty());
+}
@@ -1,4 +1,7 @@
-use capsync::tools::{all_tools, get_tool};
+use capsync::config::CustomToolConfig;
+use capsync::tools::{ToolRegistry, all_tools, get_tool, validate_registry};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
 
 #[test]
 fn test_all_tools_returns_tools() {
@@ -15,6 +18,37 @@ fn test_get_tool_valid() {
     assert_eq!(tool.name, "opencode");
 }
 
+#[test]
+fn test_get_tool_and_all_tools_include_configured_custom_tools() {
+    // The config override applies to the whole test binary, so the file is
+    // kept for other tests that look tools up while this one runs.
+    let config_dir = tempfile::TempDir::new().unwrap().keep();
+    let config_path = config_dir.join("config.toml");
+    std::fs::write(
+        &config_path,
+        r#"
+skills_source = "/tmp/capsync-skills"
+
+[destinations]
+
+[custom_tools.my-internal-agent]
+detect_path = "/opt/my-agent"
+skills_path = "/opt/my-agent/skills"
+"#,
+    )
+    .unwrap();
+    capsync::paths::set_config_override(config_path);
+
+    let tool = get_tool("my-internal-agent").unwrap();
+    assert_eq!(tool.skills_path, PathBuf::from("/opt/my-agent/skills"));
+    assert!(
+        all_tools()
+            .iter()
+            .any(|tool| tool.name == "my-internal-agent")
+    );
+    assert!(get_tool("opencode").is_some());
+}
+
 #[test]
 fn test_get_tool_invalid() {
     let tool = get_tool("nonexistent-tool");
@@ -63,7 +97,7 @@ fn test_tools_have_commands_paths() {
 #[test]
 fn test_common_tools_exist() {
     let tools = all_tools();
-    let names: Vec<_> = tools.iter().map(|t| t.name).collect();
+    let names: Vec<_> = tools.iter().map(|t| t.name.as_str()).collect();
 
     assert!(names.contains(&"opencode"));
     assert!(names.contains(&"claude"));
@@ -81,7 +115,7 @@ fn test_common_tools_exist() {
 #[test]
 fn test_external_supported_tools_exist() {
     let tools = all_tools();
-    let names: Vec<_> = tools.iter().map(|t| t.name).collect();
+    let names: Vec<_> = tools.iter().map(|t| t.name.as_str()).collect();
 
     for name in [
         "aider-desk",
@@ -180,3 +214,196 @@ fn test_supported_list_paths_match_registry() {
     assert!(zed.config_path.ends_with(".config/zed"));
     assert!(zed.skills_path.ends_with(".agents/skills"));
 }
+
+#[test]
+fn test_tools_sharing_skills_path() {
+    let registry = ToolRegistry::builtin();
+    let shared = registry.tools_sharing_skills_path("amp");
+    assert!(shared.contains(&"kimi-cli"));
+    assert!(shared.contains(&"replit"));
+    assert!(!shared.contains(&"amp"));
+
+    assert!(
+        registry
+            .tools_sharing_skills_path("claude")
+            .contains(&"claude-code")
+    );
+    assert!(
+        registry
+            .tools_sharing_skills_path("nonexistent-tool")
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_registry_includes_custom_tools() {
+    let mut custom_tools = BTreeMap::new();
+    custom_tools.insert(
+        "my-internal-agent".to_string(),
+        CustomToolConfig {
+            detect_path: PathBuf::from("~/.my-agent"),
+            skills_path: PathBuf::from("~/.my-agent/skills"),
+            commands_path: Some(PathBuf::from("/opt/my-agent/commands")),
+        },
+    );
+
+    let home = PathBuf::from("/tmp/capsync-home");
+    let registry =
+        ToolRegistry::load_in(&home, &home.join("missing-tools.toml"), &custom_tools).unwrap();
+    let tool = registry.get("my-internal-agent").unwrap();
+
+    assert_eq!(tool.config_path, home.join(".my-agent"));
+    assert_eq!(tool.skills_path, home.join(".my-agent/skills"));
+    assert_eq!(
+        tool.commands_path,
+        Some(PathBuf::from("/opt/my-agent/commands"))
+    );
+    assert!(registry.get("opencode").is_some());
+    assert_eq!(
+        registry.all().len(),
+        ToolRegistry::builtin_in(&home).all().len() + 1
+    );
+}
+
+#[test]
+fn test_custom_tool_overrides_builtin_with_same_name() {
+    let mut custom_tools = BTreeMap::new();
+    custom_tools.insert(
+        "cursor".to_string(),
+        CustomToolConfig {
+            detect_path: PathBuf::from("/opt/cursor"),
+            skills_path: PathBuf::from("/opt/cursor/skills"),
+            commands_path: None,
+        },
+    );
+
+    let home = PathBuf::from("/tmp/capsync-home");
+    let registry =
+        ToolRegistry::load_in(&home, &home.join("missing-tools.toml"), &custom_tools).unwrap();
+
+    assert_eq!(
+        registry.all().len(),
+        ToolRegistry::builtin_in(&home).all().len()
+    );
+    assert_eq!(
+        registry.get("cursor").unwrap().skills_path,
+        PathBuf::from("/opt/cursor/skills")
+    );
+}
+
+#[test]
+fn test_builtin_registry_file_is_valid() {
+    let report = validate_registry(include_str!("../src/tools.toml")).unwrap();
+
+    assert!(report.is_valid(), "{:?}", report.errors);
+    assert!(
+        report
+            .warnings
+            .iter()
+            .any(|warning| warning.contains("amp, kimi-cli, replit"))
+    );
+}
+
+#[test]
+fn test_validate_registry_reports_problems() {
+    let content = r#"
+[[tools]]
+name = "one"
+detect_path = ".one"
+skills_path = ".shared/skills"
+
+[[tools]]
+name = "one"
+detect_path = "/opt/one"
+skills_path = "~/.one/skills"
+
+[[tools]]
+name = "two"
+detect_path = ".two"
+skills_path = ".shared/skills"
+commands_path = "../outside"
+"#;
+
+    let report = validate_registry(content).unwrap();
+
+    assert!(!report.is_valid());
+    assert!(
+        report
+            .errors
+            .iter()
+            .any(|e| e.contains("Duplicate tool name 'one'"))
+    );
+    assert!(report.errors.iter().any(|e| e.contains("one.detect_path")));
+    assert!(report.errors.iter().any(|e| e.contains("one.skills_path")));
+    assert!(
+        report
+            .errors
+            .iter()
+            .any(|e| e.contains("two.commands_path"))
+    );
+    assert_eq!(report.warnings.len(), 1);
+    assert!(report.warnings[0].contains("one, two"));
+}
+
+#[test]
+fn test_validate_registry_rejects_unknown_fields() {
+    let content = r#"
+[[tools]]
+name = "one"
+detect_path = ".one"
+skill_path = ".one/skills"
+"#;
+
+    assert!(validate_registry(content).is_err());
+}
+
+#[test]
+fn test_registry_file_adds_and_overrides_tools() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let registry_path = temp_dir.path().join("tools.toml");
+    std::fs::write(
+        &registry_path,
+        r#"
+[[tools]]
+name = "cursor"
+detect_path = ".cursor-nightly"
+skills_path = ".cursor-nightly/skills"
+
+[[tools]]
+name = "new-agent"
+detect_path = ".new-agent"
+skills_path = ".new-agent/skills"
+commands_path = ".new-agent/commands"
+"#,
+    )
+    .unwrap();
+
+    let home = temp_dir.path().join("home");
+    let mut registry = ToolRegistry::builtin_in(&home);
+    registry.merge_file(&registry_path).unwrap();
+
+    assert_eq!(
+        registry.all().len(),
+        ToolRegistry::builtin_in(&home).all().len() + 1
+    );
+    assert_eq!(
+        registry.get("cursor").unwrap().skills_path,
+        home.join(".cursor-nightly/skills")
+    );
+    assert_eq!(
+        registry.get("new-agent").unwrap().commands_path,
+        Some(home.join(".new-agent/commands"))
+    );
+}
+
+#[test]
+fn test_builtin_registry_resolves_against_given_home() {
+    let home = PathBuf::from("/tmp/capsync-home");
+    let registry = ToolRegistry::builtin_in(&home);
+
+    let claude = registry.get("claude").unwrap();
+    assert_eq!(claude.config_path, home.join(".claude"));
+    assert_eq!(claude.skills_path, home.join(".claude/skills"));
+    assert_eq!(claude.commands_path, Some(home.join(".claude/commands")));
+    assert_eq!(registry.home(), home);
+}
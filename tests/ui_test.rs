@@ -0,0 +1,62 @@
+use capsync::error::CapsyncError;
+use capsync::ui::{NonInteractive, Prompter, Reporter, Scripted};
+
+#[test]
+fn test_scripted_prompter_parses_answers_like_the_terminal() {
+    let prompter = Scripted::new()
+        .answer("")
+        .answer("Y")
+        .answer("o")
+        .answer("update")
+        .answer("  ~/skills ");
+
+    assert!(!prompter.confirm("Replace?", false).unwrap());
+    assert!(prompter.confirm("Replace?", false).unwrap());
+    assert_eq!(
+        prompter
+            .choose("Update or Override?", &["update", "override"], 0)
+            .unwrap(),
+        1
+    );
+    assert_eq!(
+        prompter
+            .choose("Update or Override?", &["update", "override"], 1)
+            .unwrap(),
+        0
+    );
+    assert_eq!(prompter.ask("Source?").unwrap(), "~/skills");
+    assert_eq!(prompter.questions().len(), 5);
+}
+
+#[test]
+fn test_scripted_prompter_fails_on_missing_or_invalid_answers() {
+    let prompter = Scripted::new().answer("maybe");
+
+    let invalid = prompter.confirm("Replace?", false).unwrap_err();
+    assert!(invalid.to_string().contains("maybe"));
+
+    let missing = prompter.confirm("Replace?", false).unwrap_err();
+    assert!(missing.to_string().contains("No scripted answer"));
+}
+
+#[test]
+fn test_scripted_reporter_records_messages_and_warnings() {
+    let reporter = Scripted::new();
+
+    reporter.info("Cloning...");
+    reporter.warn("Local changes");
+
+    assert_eq!(reporter.messages(), ["Cloning..."]);
+    assert_eq!(reporter.warnings(), ["Local changes"]);
+}
+
+#[test]
+fn test_non_interactive_prompter_refuses_every_question() {
+    let error = NonInteractive.confirm("Replace it?", true).unwrap_err();
+    assert!(matches!(
+        CapsyncError::find(&error),
+        Some(CapsyncError::InputRequired { .. })
+    ));
+    assert!(NonInteractive.choose("Pick", &["one", "two"], 0).is_err());
+    assert!(NonInteractive.ask("Name?").is_err());
+}
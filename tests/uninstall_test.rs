@@ -0,0 +1,132 @@
+use capsync::config::{Config, DestinationConfig, LinkMode};
+use capsync::error::CapsyncError;
+use capsync::lockfile::{LockedSkill, Lockfile};
+use capsync::sync::SyncManager;
+use capsync::ui::Scripted;
+use capsync::uninstall::{UninstallOptions, plan_uninstall, uninstall_skill};
+use std::fs;
+use std::path::Path;
+use tempfile::tempdir;
+
+fn per_skill_config(home: &Path) -> Config {
+    let mut config = Config::for_home(home);
+    config.skills_source = home.join("skills");
+    config.destinations.clear();
+    config.destinations.insert(
+        "claude".to_string(),
+        DestinationConfig {
+            enabled: true,
+            skills_path: home.join(".claude").join("skills"),
+            commands_path: None,
+            link_mode: LinkMode::PerSkill,
+        },
+    );
+
+    for slug in ["demo", "other"] {
+        let skill_dir = config.skills_source.join(slug);
+        fs::create_dir_all(&skill_dir).unwrap();
+        fs::write(skill_dir.join("SKILL.md"), "---\nname: demo\n---\n").unwrap();
+    }
+    SyncManager::sync_skills(&config).unwrap();
+
+    config
+}
+
+fn record(config: &Config, slug: &str) {
+    let mut lockfile = Lockfile::default();
+    lockfile.insert(
+        slug,
+        LockedSkill {
+            reference: format!("owner/repo/{slug}"),
+            repo_url: "https://github.com/owner/repo.git".to_string(),
+            branch: None,
+            revision: None,
+            commit: Some("0".repeat(40)),
+            source_hash: None,
+            subpath: slug.into(),
+            content_hash: "sha1:0".to_string(),
+        },
+    );
+    lockfile.save(&config.skills_source).unwrap();
+}
+
+#[test]
+fn test_uninstall_removes_skill_links_and_lock_entry() {
+    let home = tempdir().unwrap();
+    let config = per_skill_config(home.path());
+    record(&config, "demo");
+    let link = home.path().join(".claude").join("skills").join("demo");
+    assert!(link.is_symlink());
+
+    let options = UninstallOptions {
+        slug: "demo".to_string(),
+        backup: false,
+    };
+    let result = uninstall_skill(&options, &config, &Scripted::new()).unwrap();
+
+    assert!(result.was_recorded);
+    assert_eq!(result.unlinked, vec![link.clone()]);
+    assert!(!link.is_symlink());
+    assert!(!config.skills_source.join("demo").exists());
+    assert!(config.skills_source.join("other").exists());
+    assert!(
+        home.path()
+            .join(".claude")
+            .join("skills")
+            .join("other")
+            .is_symlink()
+    );
+    assert!(
+        Lockfile::load(&config.skills_source)
+            .unwrap()
+            .get("demo")
+            .is_none()
+    );
+}
+
+#[test]
+fn test_uninstall_backup_keeps_a_hidden_copy() {
+    let home = tempdir().unwrap();
+    let config = per_skill_config(home.path());
+
+    let options = UninstallOptions {
+        slug: "demo".to_string(),
+        backup: true,
+    };
+    let result = uninstall_skill(&options, &config, &Scripted::new()).unwrap();
+
+    let backup_path = result.backup_path.unwrap();
+    assert!(!result.was_recorded);
+    assert!(backup_path.join("SKILL.md").exists());
+    assert!(
+        backup_path
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .starts_with(".demo.backup.")
+    );
+    assert!(!config.skills_source.join("demo").exists());
+}
+
+#[test]
+fn test_plan_uninstall_rejects_unknown_and_path_like_slugs() {
+    let home = tempdir().unwrap();
+    let config = per_skill_config(home.path());
+
+    for slug in ["missing", "../demo", "demo/sub"] {
+        let options = UninstallOptions {
+            slug: slug.to_string(),
+            backup: false,
+        };
+        let error = plan_uninstall(&options, &config).unwrap_err();
+        assert!(matches!(error, CapsyncError::SkillNotFound(_)), "{slug}");
+    }
+
+    let options = UninstallOptions {
+        slug: "demo".to_string(),
+        backup: false,
+    };
+    let plan = plan_uninstall(&options, &config).unwrap();
+    assert_eq!(plan.entries.len(), 2);
+    assert!(config.skills_source.join("demo").exists());
+}
@@ -0,0 +1,297 @@
+use capsync::config::Config;
+use capsync::error::CapsyncError;
+use capsync::install::{
+    ExistingSkill, InstallOptions, ResolvedInstallRef, SkillSelector, install_skill,
+    install_skill_from_checkout, locked_skill,
+};
+use capsync::lockfile::Lockfile;
+use capsync::ui::Scripted;
+use capsync::update::{UpdateOptions, UpdateStatus, update_skills};
+use std::fs;
+use std::path::Path;
+use tempfile::{TempDir, tempdir};
+
+fn write_skill(skill_dir: &Path, body: &str) {
+    fs::create_dir_all(skill_dir).unwrap();
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        format!("---\nname: demo\ndescription: test\n---\n\n{}\n", body),
+    )
+    .unwrap();
+}
+
+fn commit_all(repo_dir: &Path) -> String {
+    let repository = git2::Repository::open(repo_dir)
+        .or_else(|_| git2::Repository::init(repo_dir))
+        .unwrap();
+    let mut index = repository.index().unwrap();
+    index
+        .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+        .unwrap();
+    index.write().unwrap();
+    let tree = repository.find_tree(index.write_tree().unwrap()).unwrap();
+    let signature = git2::Signature::now("test", "test@example.com").unwrap();
+    let parent = repository
+        .head()
+        .ok()
+        .and_then(|head| head.peel_to_commit().ok());
+    let parents: Vec<_> = parent.iter().collect();
+    repository
+        .commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            "update",
+            &tree,
+            &parents,
+        )
+        .unwrap()
+        .to_string()
+}
+
+/// An upstream repository with one skill, installed into a fresh skills_source.
+fn installed_from_upstream() -> (TempDir, TempDir, Config) {
+    let upstream = tempdir().unwrap();
+    write_skill(&upstream.path().join("skills").join("demo"), "v1");
+    commit_all(upstream.path());
+
+    let home = tempdir().unwrap();
+    let mut config = Config::for_home(home.path());
+    config.skills_source = home.path().join("skills");
+
+    let checkout = tempdir().unwrap();
+    let repo_url = upstream.path().to_string_lossy().to_string();
+    capsync::git::clone_to_path(&repo_url, "", checkout.path()).unwrap();
+    let resolved = ResolvedInstallRef {
+        repo_url,
+        branch: None,
+        revision: None,
+        selector: SkillSelector::Path("skills/demo".into()),
+    };
+    let result = install_skill_from_checkout(
+        checkout.path(),
+        &resolved,
+        &config.skills_source,
+        ExistingSkill::Prompt,
+        &Scripted::new(),
+    )
+    .unwrap();
+
+    let mut lockfile = Lockfile::default();
+    lockfile.insert(
+        "demo",
+        locked_skill("local/demo", &resolved, checkout.path(), &result).unwrap(),
+    );
+    lockfile.save(&config.skills_source).unwrap();
+
+    (upstream, home, config)
+}
+
+fn update(config: &Config, force: bool, dry_run: bool) -> UpdateStatus {
+    let options = UpdateOptions {
+        slugs: vec!["demo".to_string()],
+        force,
+        dry_run,
+        ..Default::default()
+    };
+    let result = update_skills(&options, config, &Scripted::new()).unwrap();
+    assert_eq!(result.skills.len(), 1);
+    result.skills[0].status.clone()
+}
+
+#[test]
+fn test_update_replaces_skill_with_newer_upstream_commit() {
+    let (upstream, _home, config) = installed_from_upstream();
+    assert_eq!(update(&config, false, false), UpdateStatus::UpToDate);
+
+    write_skill(&upstream.path().join("skills").join("demo"), "v2");
+    let latest = commit_all(upstream.path());
+
+    assert!(matches!(
+        update(&config, false, true),
+        UpdateStatus::Available { .. }
+    ));
+    let skill_markdown = config.skills_source.join("demo").join("SKILL.md");
+    assert!(fs::read_to_string(&skill_markdown).unwrap().contains("v1"));
+
+    assert!(matches!(
+        update(&config, false, false),
+        UpdateStatus::Updated { ref to, .. } if *to == latest
+    ));
+    assert!(fs::read_to_string(&skill_markdown).unwrap().contains("v2"));
+
+    let lockfile = Lockfile::load(&config.skills_source).unwrap();
+    assert_eq!(lockfile.get("demo").unwrap().commit, Some(latest));
+    assert_eq!(lockfile.get("demo").unwrap().reference, "local/demo");
+}
+
+#[test]
+fn test_update_keeps_locally_edited_skill_unless_forced() {
+    let (upstream, _home, config) = installed_from_upstream();
+    let skill_markdown = config.skills_source.join("demo").join("SKILL.md");
+    fs::write(&skill_markdown, "---\nname: demo\n---\n\nlocal edit\n").unwrap();
+
+    write_skill(&upstream.path().join("skills").join("demo"), "v2");
+    commit_all(upstream.path());
+
+    assert!(matches!(
+        update(&config, false, false),
+        UpdateStatus::LocallyModified { .. }
+    ));
+    assert!(
+        fs::read_to_string(&skill_markdown)
+            .unwrap()
+            .contains("local edit")
+    );
+
+    assert!(matches!(
+        update(&config, true, false),
+        UpdateStatus::Updated { .. }
+    ));
+    assert!(fs::read_to_string(&skill_markdown).unwrap().contains("v2"));
+}
+
+#[test]
+fn test_update_rejects_skills_without_a_lock_entry() {
+    let (_upstream, _home, config) = installed_from_upstream();
+    let options = UpdateOptions {
+        slugs: vec!["unknown".to_string()],
+        ..Default::default()
+    };
+
+    let error = update_skills(&options, &config, &Scripted::new()).unwrap_err();
+    assert!(matches!(error, CapsyncError::SkillNotFound(_)));
+}
+
+#[test]
+fn test_update_requires_slugs_or_all() {
+    let (upstream, _home, config) = installed_from_upstream();
+    write_skill(&upstream.path().join("skills").join("demo"), "v2");
+    commit_all(upstream.path());
+
+    let neither = UpdateOptions::default();
+    assert!(update_skills(&neither, &config, &Scripted::new()).is_err());
+    let both = UpdateOptions {
+        slugs: vec!["demo".to_string()],
+        all: true,
+        ..Default::default()
+    };
+    assert!(update_skills(&both, &config, &Scripted::new()).is_err());
+
+    let all = UpdateOptions {
+        all: true,
+        dry_run: true,
+        ..Default::default()
+    };
+    let result = update_skills(&all, &config, &Scripted::new()).unwrap();
+    assert_eq!(result.skills.len(), 1);
+    assert!(matches!(
+        result.skills[0].status,
+        UpdateStatus::Available { .. }
+    ));
+}
+
+#[test]
+fn test_update_reinstalls_changed_skills_from_a_local_directory() {
+    let shared = tempdir().unwrap();
+    let skill_dir = shared.path().join("demo");
+    write_skill(&skill_dir, "v1");
+
+    let home = tempdir().unwrap();
+    let mut config = Config::for_home(home.path());
+    config.skills_source = home.path().join("skills");
+    let options = InstallOptions {
+        reference: skill_dir.to_string_lossy().to_string(),
+        existing: ExistingSkill::Prompt,
+        revision: None,
+    };
+    install_skill(&options, &config, &Scripted::new(), &Scripted::new()).unwrap();
+    assert_eq!(update(&config, false, false), UpdateStatus::UpToDate);
+
+    write_skill(&skill_dir, "v2");
+    assert!(matches!(
+        update(&config, false, false),
+        UpdateStatus::Updated { .. }
+    ));
+    let installed = fs::read_to_string(config.skills_source.join("demo").join("SKILL.md")).unwrap();
+    assert!(installed.contains("v2"));
+    assert_eq!(update(&config, false, false), UpdateStatus::UpToDate);
+}
+
+#[test]
+fn test_update_reinstalls_skills_from_a_non_ascii_path() {
+    let shared = tempdir().unwrap();
+    let skill_dir = shared.path().join("josé").join("demo");
+    write_skill(&skill_dir, "v1");
+
+    let home = tempdir().unwrap();
+    let mut config = Config::for_home(home.path());
+    config.skills_source = home.path().join("skills");
+    let options = InstallOptions {
+        reference: skill_dir.to_string_lossy().to_string(),
+        existing: ExistingSkill::Prompt,
+        revision: None,
+    };
+    install_skill(&options, &config, &Scripted::new(), &Scripted::new()).unwrap();
+    let lockfile = Lockfile::load(&config.skills_source).unwrap();
+    assert!(lockfile.get("demo").unwrap().repo_url.contains("jos%C3%A9"));
+
+    write_skill(&skill_dir, "v2");
+    assert!(matches!(
+        update(&config, false, false),
+        UpdateStatus::Updated { .. }
+    ));
+    let installed = fs::read_to_string(config.skills_source.join("demo").join("SKILL.md")).unwrap();
+    assert!(installed.contains("v2"));
+}
+
+#[test]
+fn test_install_pins_a_tag_or_commit_and_update_stays_on_it() {
+    let upstream = tempdir().unwrap();
+    let repo_dir = upstream.path().join("team").join("skills.git");
+    write_skill(&repo_dir.join("skills").join("demo"), "v1");
+    let tagged = commit_all(&repo_dir);
+    let repository = git2::Repository::open(&repo_dir).unwrap();
+    let tag_target = repository
+        .find_object(git2::Oid::from_str(&tagged).unwrap(), None)
+        .unwrap();
+    repository
+        .tag_lightweight("v1", &tag_target, false)
+        .unwrap();
+    write_skill(&repo_dir.join("skills").join("demo"), "v2");
+    let latest = commit_all(&repo_dir);
+
+    let home = tempdir().unwrap();
+    let mut config = Config::for_home(home.path());
+    config.skills_source = home.path().join("skills");
+    config.default_host = Some(format!("file://{}", upstream.path().display()));
+    let install = |reference: &str, revision: Option<&str>| {
+        let options = InstallOptions {
+            reference: reference.to_string(),
+            existing: ExistingSkill::Replace,
+            revision: revision.map(str::to_string),
+        };
+        install_skill(&options, &config, &Scripted::new(), &Scripted::new())
+    };
+    let skill_markdown = config.skills_source.join("demo").join("SKILL.md");
+
+    install("team/skills/demo@v1", None).unwrap();
+    assert!(fs::read_to_string(&skill_markdown).unwrap().contains("v1"));
+    let locked = Lockfile::load(&config.skills_source).unwrap();
+    let locked = locked.get("demo").unwrap();
+    assert_eq!(locked.revision.as_deref(), Some("v1"));
+    assert_eq!(locked.commit, Some(tagged));
+    assert_eq!(locked.branch, None);
+    assert_eq!(update(&config, false, false), UpdateStatus::UpToDate);
+
+    install("team/skills/demo", Some(&latest)).unwrap();
+    assert!(fs::read_to_string(&skill_markdown).unwrap().contains("v2"));
+    let locked = Lockfile::load(&config.skills_source).unwrap();
+    assert_eq!(locked.get("demo").unwrap().commit, Some(latest));
+
+    let error = install("team/skills/demo@v1", Some("main")).unwrap_err();
+    assert!(matches!(error, CapsyncError::InvalidReference(_)));
+    let error = install("team/skills/demo@v9", None).unwrap_err();
+    assert!(matches!(error, CapsyncError::InvalidReference(_)));
+    assert_eq!(error.exit_code(), 5);
+}
@@ -0,0 +1,169 @@
+use capsync::install::{
+    ExistingSkill, ResolvedInstallRef, SkillSelector, install_skill_from_checkout,
+};
+use capsync::ui::Scripted;
+use capsync::validate::Severity;
+use capsync::validate::{Frontmatter, FrontmatterError, validate_path, validate_skill};
+use std::fs;
+use std::path::Path;
+use tempfile::tempdir;
+
+fn write_skill(skill_dir: &Path, content: &str) {
+    fs::create_dir_all(skill_dir).unwrap();
+    fs::write(skill_dir.join("SKILL.md"), content).unwrap();
+}
+
+fn messages(skill_dir: &Path) -> Vec<(Severity, Option<usize>, String)> {
+    validate_skill(skill_dir)
+        .unwrap()
+        .into_iter()
+        .map(|problem| (problem.severity, problem.line, problem.message))
+        .collect()
+}
+
+#[test]
+fn test_frontmatter_parses_multiline_and_quoted_values() {
+    let content = "---\nname: demo\ndescription: >\n  Folded text\n  over lines\nnote: \"a: b\"\n---\n# Body\n";
+    let frontmatter = Frontmatter::parse(content).unwrap().unwrap();
+
+    assert_eq!(frontmatter.get_str("name"), Some("demo"));
+    assert_eq!(
+        frontmatter.get_str("description"),
+        Some("Folded text over lines\n")
+    );
+    assert_eq!(frontmatter.get_str("note"), Some("a: b"));
+    assert_eq!(frontmatter.line_of("description"), 3);
+
+    assert!(Frontmatter::parse("# No frontmatter\n").unwrap().is_none());
+    assert_eq!(
+        Frontmatter::parse("---\nname: demo\n").unwrap_err(),
+        FrontmatterError::Unterminated
+    );
+    assert_eq!(
+        Frontmatter::parse("---\n- a\n- b\n---\n").unwrap_err(),
+        FrontmatterError::NotMapping
+    );
+    assert!(matches!(
+        Frontmatter::parse("---\nname: demo\ndescription: [unclosed\n---\n").unwrap_err(),
+        FrontmatterError::Syntax { .. }
+    ));
+}
+
+#[test]
+fn test_validate_skill_accepts_a_well_formed_skill() {
+    let root = tempdir().unwrap();
+    let skill_dir = root.path().join("demo");
+    write_skill(
+        &skill_dir,
+        "---\nname: demo\ndescription: Does things\n---\n\nSee [the guide](guide.md#setup).\n\n```\n[not](a-link.md)\n```\n",
+    );
+    fs::write(skill_dir.join("guide.md"), "guide").unwrap();
+
+    assert!(messages(&skill_dir).is_empty());
+}
+
+#[test]
+fn test_validate_skill_reports_every_problem_with_its_line() {
+    let root = tempdir().unwrap();
+    let skill_dir = root.path().join("demo");
+    write_skill(
+        &skill_dir,
+        "---\nlicense: MIT\nname: Demo Skill\n---\n\nRun [the script](scripts/run.sh) or see [docs](https://example.com).\n",
+    );
+
+    let problems = messages(&skill_dir);
+
+    assert_eq!(problems.len(), 4, "{problems:?}");
+    assert!(problems.contains(&(
+        Severity::Error,
+        Some(1),
+        "frontmatter has no 'description'".to_string()
+    )));
+    assert!(problems.iter().any(|(severity, line, message)| {
+        *severity == Severity::Warning && *line == Some(3) && message.contains("'demo-skill'")
+    }));
+    assert!(problems.iter().any(|(severity, line, message)| {
+        *severity == Severity::Warning
+            && *line == Some(3)
+            && message.contains("the directory is 'demo'")
+    }));
+    assert!(problems.contains(&(
+        Severity::Warning,
+        Some(6),
+        "links to scripts/run.sh, which does not exist".to_string()
+    )));
+}
+
+#[test]
+fn test_validate_skill_reports_syntax_errors_and_limits() {
+    let root = tempdir().unwrap();
+
+    let broken = root.path().join("broken");
+    write_skill(&broken, "---\nname: broken\ndescription: [oops\n---\n");
+    let problems = messages(&broken);
+    assert_eq!(problems.len(), 1);
+    assert_eq!(problems[0].0, Severity::Error);
+    assert!(problems[0].2.starts_with("frontmatter is not valid YAML"));
+    assert!(problems[0].1.unwrap() >= 3);
+
+    let long = root.path().join("long");
+    write_skill(
+        &long,
+        &format!("---\nname: long\ndescription: {}\n---\n", "x".repeat(1025)),
+    );
+    let problems = messages(&long);
+    assert_eq!(problems.len(), 1);
+    assert_eq!(problems[0].1, Some(3));
+    assert!(problems[0].2.contains("the limit is 1024"));
+
+    let typed = root.path().join("typed");
+    write_skill(&typed, "---\nname: 42\ndescription: ok\n---\n");
+    assert_eq!(messages(&typed)[0].2, "'name' must be a string");
+}
+
+#[test]
+fn test_validate_path_walks_a_skills_directory() {
+    let root = tempdir().unwrap();
+    write_skill(
+        &root.path().join("good"),
+        "---\nname: good\ndescription: Works\n---\n",
+    );
+    write_skill(&root.path().join("group").join("bad"), "# nothing\n");
+    write_skill(&root.path().join(".bad.backup.1"), "# ignored\n");
+
+    let report = validate_path(root.path()).unwrap();
+    assert_eq!(report.skills_checked, 2);
+    assert_eq!(report.count(Severity::Error), 1);
+
+    let single = validate_path(&root.path().join("good").join("SKILL.md")).unwrap();
+    assert_eq!(single.skills_checked, 1);
+    assert!(single.problems.is_empty());
+}
+
+#[test]
+fn test_install_refuses_skills_that_fail_validation() {
+    let checkout_dir = tempdir().unwrap();
+    let target_dir = tempdir().unwrap();
+    write_skill(
+        &checkout_dir.path().join("skills").join("demo"),
+        "---\nname: demo\n---\n",
+    );
+
+    let resolved = ResolvedInstallRef {
+        repo_url: "https://github.com/owner/repo.git".to_string(),
+        branch: None,
+        revision: None,
+        selector: SkillSelector::Path("skills/demo".into()),
+    };
+    let error = install_skill_from_checkout(
+        checkout_dir.path(),
+        &resolved,
+        target_dir.path(),
+        ExistingSkill::Prompt,
+        &Scripted::new(),
+    )
+    .unwrap_err();
+
+    assert!(error.to_string().contains("no 'description'"));
+    assert!(!target_dir.path().join("demo").exists());
+}